@@ -2,8 +2,11 @@ use clap::ArgMatches;
 use failure::Error;
 use lang::{OsStrX, Str};
 use settings::{Settings, Setup};
-use todo::command::store::fs::{Create, Find};
-use todo::command::{Command, List, New};
+use todo::command::store::fs::{Create, CreateAttr, Edit, Find, Migrate, Move, Remove, Restore, Undo, Update};
+use todo::command::{
+    Agenda, Check, Close, Command, Comment, Export, History, Import, Link, List, Log, New, Report, Show,
+    Start, Stop, Tag, Tags, Tree,
+};
 use todo::error::TodoError;
 use todo::issue::Issue;
 
@@ -27,11 +30,53 @@ macro_rules! commands {
     };
 }
 
+macro_rules! subcommands {
+    ($([$const_name:ident] $name:ident $desc:tt),*) => {
+        impl Cmd {
+            $(
+            pub const $const_name: Cmd = Cmd {
+                name: stringify!($name),
+                short: "",
+                desc: $desc
+            };
+            )*
+
+            pub const SUBCOMMANDS: &'static [Cmd] = &[$(Cmd::$const_name),*];
+        }
+    };
+}
+
 commands! {
     [NEW] new, -n, --new    "Create new issue",
     [LIST] list, -l, --list "List issues"
 }
 
+subcommands! {
+    [TAG] tag       "Add (+tag) or remove (-tag) issue tags",
+    [TAGS] tags     "List tags with issues count",
+    [AGENDA] agenda "List issues due this week",
+    [CLOSE] close   "Close issue and create the next one for recurring issues",
+    [LINK] link     "Link issues: <id> blocks <id> or <id> blocked_by <id>",
+    [SHOW] show     "Show issue with its dependency chain and subtasks",
+    [TREE] tree     "Print issues hierarchy with subtasks progress",
+    [CHECK] check   "Tick (+n) or untick (-n) issue checklist items",
+    [START] start   "Start tracking time on issue",
+    [STOP] stop     "Stop tracking time",
+    [LOG] log       "Log time spent on issue, e.g. 1h30m",
+    [REPORT] report "Report time spent per issue or attr over a date range",
+    [COMMENT] comment "Append comment to issue",
+    [HISTORY] history "Show issue attrs change history",
+    [UNDO] undo       "Revert the last operation",
+    [EDIT] edit       "Open issue in editor and save changes",
+    [MV] mv           "Move issue file to the path rendered from changed attrs",
+    [RM] rm           "Move issue to trash or delete it permanently with --purge",
+    [TRASH] trash     "List issues in trash",
+    [RESTORE] restore "Bring issue back from trash",
+    [MIGRATE] migrate "Move issue files from one filename format to another",
+    [EXPORT] export   "Export issues as JSON, todo.txt or Taskwarrior JSON",
+    [IMPORT] import   "Import issues from JSON, todo.txt or Taskwarrior JSON file or stdin"
+}
+
 impl Cmd {
     pub fn process(
         &self,
@@ -47,6 +92,9 @@ impl Cmd {
                 New {
                     create: Some(Create::default().setup(settings)),
                     issue,
+                    read_stdin: New::<Create>::is_stdin_redirected_from_file(),
+                    stdin_piped: New::<Create>::is_stdin_piped(),
+                    ..Default::default()
                 }.setup(&settings),
             );
         } else if self.name == Cmd::LIST.name {
@@ -56,6 +104,187 @@ impl Cmd {
                     issue,
                 }.setup(&settings)
             );
+        } else if self.name == Cmd::TAG.name {
+            cmd = Box::new(
+                Tag {
+                    update: Some(Update::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::TAGS.name {
+            cmd = Box::new(
+                Tags {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::AGENDA.name {
+            cmd = Box::new(
+                Agenda {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::CLOSE.name {
+            let new = New {
+                create: Some(Create::default().setup(settings)),
+                issue: issue.clone(),
+                ..Default::default()
+            }.setup(&settings);
+            cmd = Box::new(
+                Close {
+                    update: Some(Update::default().setup(settings)),
+                    new: Some(new),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::LINK.name {
+            cmd = Box::new(
+                Link {
+                    find: Some(Find::default().setup(settings)),
+                    update: Some(Update::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::SHOW.name {
+            cmd = Box::new(
+                Show {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::TREE.name {
+            cmd = Box::new(
+                Tree {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::CHECK.name {
+            cmd = Box::new(
+                Check {
+                    update: Some(Update::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::START.name {
+            cmd = Box::new(
+                Start {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::STOP.name {
+            cmd = Box::new(Stop::default().setup(&settings));
+        } else if self.name == Cmd::LOG.name {
+            cmd = Box::new(
+                Log {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::REPORT.name {
+            cmd = Box::new(
+                Report {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::COMMENT.name {
+            cmd = Box::new(
+                Comment {
+                    update: Some(Update::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::HISTORY.name {
+            cmd = Box::new(
+                History {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::EDIT.name {
+            cmd = Box::new(
+                Edit {
+                    update: Some(Update::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::MV.name {
+            cmd = Box::new(
+                Move {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::RM.name {
+            cmd = Box::new(
+                Remove {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::TRASH.name {
+            let mut find = Find::default().setup(settings);
+            find.trash = true;
+            cmd = Box::new(
+                List {
+                    find: Some(find),
+                    issue,
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::RESTORE.name {
+            cmd = Box::new(
+                Restore {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::MIGRATE.name {
+            cmd = Box::new(
+                Migrate {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::EXPORT.name {
+            cmd = Box::new(
+                Export {
+                    find: Some(Find::default().setup(settings)),
+                    issue,
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::IMPORT.name {
+            let new = New {
+                create: Some(Create::default().setup(settings)),
+                issue,
+                ..Default::default()
+            }.setup(&settings);
+            cmd = Box::new(
+                Import {
+                    new: Some(new),
+                    ..Default::default()
+                }.setup(&settings)
+            );
+        } else if self.name == Cmd::UNDO.name {
+            cmd = Box::new(Undo::default().setup(&settings));
         } else {
             return Err(TodoError::UnknownCommand {
                 name: self.name.to_string(),
@@ -68,7 +297,21 @@ impl Cmd {
                 cmd.set_param(key.as_str(), value.as_str().to_string())?;
             }
         }
-        cmd.exec();
-        Ok(())
+        if matches.is_present(::EDIT_ARG_NAME) {
+            cmd.set_param(CreateAttr::Edit.key(), true.to_string())?;
+        }
+        if matches.is_present(::PURGE_ARG_NAME) {
+            cmd.set_param(Remove::PURGE_PARAM, true.to_string())?;
+        }
+        for &(arg_name, param) in &[
+            (::FROM_FORMAT_ARG_NAME, Migrate::FROM_FORMAT_PARAM),
+            (::TO_FORMAT_ARG_NAME, Migrate::TO_FORMAT_PARAM),
+            (::FORMAT_ARG_NAME, Import::<Create>::FORMAT_PARAM),
+        ] {
+            if let Some(value) = matches.value_of(arg_name) {
+                cmd.set_param(param, value.to_string())?;
+            }
+        }
+        cmd.exec()
     }
 }
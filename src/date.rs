@@ -0,0 +1,378 @@
+use chrono::{Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+
+use lang::Str;
+
+pub const DATE_FORMAT: Str = "%Y-%m-%d";
+pub const DATETIME_FORMAT: Str = "%Y-%m-%d %H:%M:%S";
+
+const WEEKDAYS: [(Weekday, &[&str]); 7] = [
+    (Weekday::Mon, &["mon", "monday"]),
+    (Weekday::Tue, &["tue", "tues", "tuesday"]),
+    (Weekday::Wed, &["wed", "wednesday"]),
+    (Weekday::Thu, &["thu", "thur", "thurs", "thursday"]),
+    (Weekday::Fri, &["fri", "friday"]),
+    (Weekday::Sat, &["sat", "saturday"]),
+    (Weekday::Sun, &["sun", "sunday"]),
+];
+
+#[derive(Clone, Debug, Default)]
+pub struct Calendar {
+    pub offset: Option<FixedOffset>,
+}
+
+impl Calendar {
+    pub fn now(&self) -> NaiveDateTime {
+        match self.offset {
+            Some(offset) => Utc::now().with_timezone(&offset).naive_local(),
+            None => Local::now().naive_local(),
+        }
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.now().date()
+    }
+
+    pub fn timestamp(&self) -> String {
+        self.now().format(DATETIME_FORMAT).to_string()
+    }
+
+    pub fn end_of_week(&self) -> NaiveDate {
+        let today = self.today();
+        today + Duration::days(6 - i64::from(today.weekday().num_days_from_monday()))
+    }
+
+    pub fn parse_date<S: AsRef<str>>(&self, input: S) -> Option<NaiveDate> {
+        parse_date_from(input.as_ref(), self.today())
+    }
+
+    pub fn to_utc(&self, datetime: NaiveDateTime) -> NaiveDateTime {
+        match self.offset {
+            Some(offset) => datetime - Duration::seconds(i64::from(offset.local_minus_utc())),
+            None => Local.from_local_datetime(&datetime)
+                .earliest()
+                .map(|local| local.naive_utc())
+                .unwrap_or(datetime),
+        }
+    }
+
+    pub fn from_utc(&self, datetime: NaiveDateTime) -> NaiveDateTime {
+        match self.offset {
+            Some(offset) => datetime + Duration::seconds(i64::from(offset.local_minus_utc())),
+            None => Local.from_utc_datetime(&datetime).naive_local(),
+        }
+    }
+}
+
+pub fn parse_offset(input: &str) -> Option<FixedOffset> {
+    let input = input.trim();
+    if ["utc", "gmt", "z"].contains(&input.to_lowercase().as_str()) {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, rest) = match input.chars().next()? {
+        '+' => (1, &input[1..]),
+        '-' => (-1, &input[1..]),
+        _ => return None,
+    };
+    let digits: String = rest.chars().filter(|ch| *ch != ':').collect();
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.parse::<i32>().ok()?, 0),
+        4 => (digits[..2].parse::<i32>().ok()?, digits[2..].parse::<i32>().ok()?),
+        _ => return None,
+    };
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+pub fn format_date(date: NaiveDate) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+pub fn add_months(date: NaiveDate, months: i32) -> Option<NaiveDate> {
+    let total = date.year() * 12 + date.month0() as i32 + months;
+    let (year, month) = (total / 12, (total % 12) as u32 + 1);
+    (1..=date.day())
+        .rev()
+        .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .next()
+}
+
+pub fn parse_date_from(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+
+    match input.as_str() {
+        "today" => return Some(today),
+        "tomorrow" => return Some(today + Duration::days(1)),
+        "yesterday" => return Some(today - Duration::days(1)),
+        _ => (),
+    }
+
+    for &(weekday, names) in WEEKDAYS.iter() {
+        if names.contains(&input.as_str()) {
+            let days = (7 + i64::from(weekday.num_days_from_monday())
+                - i64::from(today.weekday().num_days_from_monday())) % 7;
+            return Some(today + Duration::days(if days == 0 { 7 } else { days }));
+        }
+    }
+
+    if input.starts_with('+') || input.starts_with('-') {
+        let sign = if input.starts_with('-') { -1 } else { 1 };
+        let rest = &input[1..];
+        let number = rest.trim_end_matches(char::is_alphabetic);
+        if number.is_empty() {
+            return None;
+        }
+        let count = number.parse::<i32>().ok()? * sign;
+        return match &rest[number.len()..] {
+            "d" | "" => Some(today + Duration::days(i64::from(count))),
+            "w" => Some(today + Duration::weeks(i64::from(count))),
+            "m" => add_months(today, count),
+            "y" => add_months(today, count * 12),
+            _ => None,
+        };
+    }
+
+    NaiveDate::parse_from_str(&input, DATE_FORMAT).ok()
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DateFilter {
+    None,
+    Overdue,
+    Today,
+    Week,
+    Agenda,
+    Upcoming,
+    Date(NaiveDate),
+}
+
+impl DateFilter {
+    pub fn parse<S: AsRef<str>>(input: S, calendar: &Calendar) -> Option<Self> {
+        Some(match input.as_ref().trim() {
+            "" => DateFilter::None,
+            "overdue" => DateFilter::Overdue,
+            "today" => DateFilter::Today,
+            "week" => DateFilter::Week,
+            "agenda" => DateFilter::Agenda,
+            "upcoming" => DateFilter::Upcoming,
+            input => DateFilter::Date(calendar.parse_date(input)?),
+        })
+    }
+
+    pub fn is_match(&self, value: Option<&str>, calendar: &Calendar) -> bool {
+        let date = value.and_then(|value| NaiveDate::parse_from_str(value.trim(), DATE_FORMAT).ok());
+        match (self, date) {
+            (&DateFilter::None, None) => true,
+            (_, None) | (&DateFilter::None, _) => false,
+            (&DateFilter::Overdue, Some(date)) => date < calendar.today(),
+            (&DateFilter::Today, Some(date)) => date == calendar.today(),
+            (&DateFilter::Week, Some(date)) => date >= calendar.today() && date <= calendar.end_of_week(),
+            // Overdue items stay on the agenda until they are done
+            (&DateFilter::Agenda, Some(date)) => date <= calendar.end_of_week(),
+            (&DateFilter::Upcoming, Some(date)) => {
+                let today = calendar.today();
+                date >= today && date <= today + Duration::days(7)
+            }
+            (&DateFilter::Date(expected), Some(date)) => date == expected,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Recurrence {
+    Days(i64),
+    Months(i32),
+    Weekdays(Vec<Weekday>),
+    MonthDay(u32),
+    Cron {
+        days: Option<Vec<u32>>,
+        months: Option<Vec<u32>>,
+        weekdays: Option<Vec<u32>>,
+    },
+}
+
+impl Recurrence {
+    const CRON_SEARCH_DAYS: i64 = 366 * 5;
+
+    pub fn parse<S: AsRef<str>>(input: S) -> Option<Self> {
+        let input = input.as_ref().trim().to_lowercase();
+        let words: Vec<&str> = input.split_whitespace().collect();
+
+        Some(match words.as_slice() {
+            ["daily"] => Recurrence::Days(1),
+            ["weekly"] => Recurrence::Days(7),
+            ["monthly"] => Recurrence::Months(1),
+            ["yearly"] | ["annually"] => Recurrence::Months(12),
+            ["weekly", "on", days] => Recurrence::Weekdays(
+                days.split(',').map(parse_weekday).collect::<Option<Vec<_>>>()?
+            ),
+            ["monthly", "on", day] => match day.parse::<u32>().ok()? {
+                day @ 1..=31 => Recurrence::MonthDay(day),
+                _ => return None,
+            },
+            ["every", unit] => Self::every(1, unit)?,
+            ["every", count, unit] => Self::every(count.parse().ok()?, unit)?,
+            [_, _, days, months, weekdays] => Recurrence::Cron {
+                days: parse_cron_field(days, 1, 31)?,
+                months: parse_cron_field(months, 1, 12)?,
+                weekdays: parse_cron_field(weekdays, 0, 7)?
+                    .map(|days| days.into_iter().map(|day| day % 7).collect()),
+            },
+            _ => return None,
+        })
+    }
+
+    fn every(count: i64, unit: &str) -> Option<Self> {
+        if count < 1 {
+            return None;
+        }
+        Some(match unit.trim_end_matches('s') {
+            "day" => Recurrence::Days(count),
+            "week" => Recurrence::Days(count * 7),
+            "month" => Recurrence::Months(count as i32),
+            "year" => Recurrence::Months(count as i32 * 12),
+            unit => Recurrence::Weekdays(vec![parse_weekday(unit)?]),
+        })
+    }
+
+    pub fn next_after(&self, date: NaiveDate) -> Option<NaiveDate> {
+        match *self {
+            Recurrence::Days(days) => Some(date + Duration::days(days)),
+            Recurrence::Months(months) => add_months(date, months),
+            _ => (1..=Self::CRON_SEARCH_DAYS)
+                .map(|days| date + Duration::days(days))
+                .find(|date| self.is_match(*date)),
+        }
+    }
+
+    fn is_match(&self, date: NaiveDate) -> bool {
+        let contains = |values: &Option<Vec<u32>>, value| {
+            values.as_ref().map(|values| values.contains(&value))
+        };
+        match *self {
+            Recurrence::Weekdays(ref weekdays) => weekdays.contains(&date.weekday()),
+            Recurrence::MonthDay(day) => {
+                let last_day = add_months(date.with_day(1).unwrap_or(date), 1)
+                    .map(|next| (next - Duration::days(1)).day())
+                    .unwrap_or(31);
+                date.day() == day.min(last_day)
+            }
+            Recurrence::Cron { ref days, ref months, ref weekdays } => {
+                let day = contains(days, date.day());
+                let weekday = contains(weekdays, date.weekday().num_days_from_sunday());
+                contains(months, date.month()).unwrap_or(true) && match (day, weekday) {
+                    (Some(day), Some(weekday)) => day || weekday,
+                    (day, weekday) => day.or(weekday).unwrap_or(true),
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    WEEKDAYS.iter()
+        .find(|&&(_, names)| names.contains(&input))
+        .map(|&(weekday, _)| weekday)
+}
+
+fn parse_cron_field(field: &str, min: u32, max: u32) -> Option<Option<Vec<u32>>> {
+    if field == "*" {
+        return Some(None);
+    }
+
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.find('/') {
+            Some(pos) => (&part[..pos], part[(pos + 1)..].parse::<usize>().ok()?),
+            None => (part, 1),
+        };
+        let (from, to) = match (range, range.find('-')) {
+            ("*", _) => (min, max),
+            (range, Some(pos)) => (range[..pos].parse().ok()?, range[(pos + 1)..].parse().ok()?),
+            (range, None) => {
+                let value = range.parse().ok()?;
+                (value, if step > 1 { max } else { value })
+            }
+        };
+        if step == 0 || from < min || to > max || from > to {
+            return None;
+        }
+        values.extend((from..=to).step_by(step));
+    }
+    Some(Some(values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_date() {
+        // Monday
+        let today = NaiveDate::from_ymd_opt(2018, 6, 4).unwrap();
+        let date = |y, m, d| Some(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+
+        assert_eq!(date(2018, 6, 4), parse_date_from("today", today));
+        assert_eq!(date(2018, 6, 5), parse_date_from("Tomorrow", today));
+        assert_eq!(date(2018, 6, 3), parse_date_from("yesterday", today));
+        assert_eq!(date(2018, 6, 8), parse_date_from("fri", today));
+        assert_eq!(date(2018, 6, 8), parse_date_from("friday", today));
+        assert_eq!(date(2018, 6, 11), parse_date_from("mon", today));
+        assert_eq!(date(2018, 6, 7), parse_date_from("+3d", today));
+        assert_eq!(date(2018, 6, 7), parse_date_from("+3", today));
+        assert_eq!(date(2018, 6, 1), parse_date_from("-3d", today));
+        assert_eq!(date(2018, 6, 18), parse_date_from("+2w", today));
+        assert_eq!(date(2018, 7, 4), parse_date_from("+1m", today));
+        assert_eq!(date(2019, 6, 4), parse_date_from("+1y", today));
+        assert_eq!(date(2018, 12, 31), parse_date_from("2018-12-31", today));
+        assert_eq!(None, parse_date_from("2018-13-31", today));
+        assert_eq!(None, parse_date_from("someday", today));
+        assert_eq!(None, parse_date_from("+d", today));
+        assert_eq!(None, parse_date_from("+", today));
+        assert_eq!(None, parse_date_from("-", today));
+        assert_eq!(None, parse_date_from("+3é", today));
+        assert_eq!(None, parse_date_from("+3dd", today));
+
+        let jan_31 = NaiveDate::from_ymd_opt(2018, 1, 31).unwrap();
+        assert_eq!(date(2018, 2, 28), add_months(jan_31, 1));
+        assert_eq!(date(2017, 12, 31), add_months(jan_31, -1));
+    }
+
+    #[test]
+    fn parse_offset() {
+        assert_eq!(FixedOffset::east_opt(0), super::parse_offset("UTC"));
+        assert_eq!(FixedOffset::east_opt(3 * 3600), super::parse_offset("+03:00"));
+        assert_eq!(FixedOffset::east_opt(-(5 * 3600 + 1800)), super::parse_offset("-0530"));
+        assert_eq!(FixedOffset::east_opt(2 * 3600), super::parse_offset("+2"));
+        assert_eq!(None, super::parse_offset("Europe/Moscow"));
+    }
+
+    #[test]
+    fn recurrence() {
+        // Monday
+        let today = NaiveDate::from_ymd_opt(2018, 6, 4).unwrap();
+        let date = |y, m, d| Some(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        let next = |input: &str| Recurrence::parse(input).and_then(|recur| recur.next_after(today));
+
+        assert_eq!(date(2018, 6, 5), next("daily"));
+        assert_eq!(date(2018, 6, 11), next("weekly"));
+        assert_eq!(date(2018, 7, 4), next("monthly"));
+        assert_eq!(date(2019, 6, 4), next("yearly"));
+        assert_eq!(date(2018, 6, 18), next("every 2 weeks"));
+        assert_eq!(date(2018, 6, 7), next("every 3 days"));
+        assert_eq!(date(2018, 6, 8), next("every friday"));
+        assert_eq!(date(2018, 6, 11), next("weekly on mon"));
+        assert_eq!(date(2018, 6, 6), next("weekly on wed,fri"));
+        assert_eq!(date(2018, 7, 1), next("monthly on 1"));
+        assert_eq!(date(2018, 6, 30), next("monthly on 31"));
+        assert_eq!(date(2018, 7, 1), next("0 9 1 * *"));
+        assert_eq!(date(2018, 6, 9), next("0 9 * * 6,0"));
+        assert_eq!(date(2018, 6, 10), next("0 9 * * 7"));
+        assert_eq!(date(2018, 6, 5), next("0 9 */5 * 1-5"));
+        assert_eq!(date(2019, 1, 1), next("* * 1 1 *"));
+        assert_eq!(None, next("sometimes"));
+        assert_eq!(None, next("monthly on 32"));
+        assert_eq!(None, next("every 0 days"));
+        assert_eq!(None, next("0 9 * 13 *"));
+    }
+}
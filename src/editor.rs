@@ -0,0 +1,54 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::process::{self, Command};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use failure::Error;
+
+use todo::error::TodoError;
+
+const DEFAULT_EDITOR: &str = "vi";
+
+pub fn editor_command(configured: Option<&String>) -> String {
+    let not_empty = |editor: &String| !editor.trim().is_empty();
+
+    configured.cloned()
+        .filter(&not_empty)
+        .or_else(|| env::var("VISUAL").ok().filter(&not_empty))
+        .or_else(|| env::var("EDITOR").ok().filter(&not_empty))
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string())
+}
+
+// The file must be new, so a file or symlink planted at a guessed path is never written through
+fn create_temp_file(text: &str, ext: &str) -> Result<PathBuf, Error> {
+    loop {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.subsec_nanos())
+            .unwrap_or_default();
+        let path = env::temp_dir().join(format!("todo-{}-{}.{}", process::id(), nanos, ext));
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                file.write_all(text.as_bytes())?;
+                return Ok(path);
+            }
+            Err(ref err) if err.kind() == ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+pub fn edit_text(editor: &str, text: &str, ext: &str) -> Result<String, Error> {
+    let path = create_temp_file(text, ext)?;
+
+    let mut args = editor.split_whitespace();
+    let program = args.next().unwrap_or(DEFAULT_EDITOR);
+    let edited = match Command::new(program).args(args).arg(&path).status() {
+        Ok(ref status) if status.success() => fs::read_to_string(&path).map_err(Error::from),
+        _ => Err(TodoError::EditorFailed { editor: editor.to_string() }.into()),
+    };
+    let _ = fs::remove_file(&path);
+    edited
+}
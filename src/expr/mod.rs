@@ -3,6 +3,8 @@ mod parser;
 use nom::types::CompleteStr;
 use failure::Error;
 
+use todo::error::TodoError;
+
 #[derive(Debug, PartialEq)]
 pub enum Expr {
     Or(Box<Expr>, Box<Expr>),
@@ -22,7 +24,7 @@ impl Expr {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Debug)]
 pub struct Ops {
     pub lp: String,
     pub rp: String,
@@ -31,6 +33,18 @@ pub struct Ops {
     pub not: String,
 }
 
+impl Default for Ops {
+    fn default() -> Self {
+        Ops {
+            lp: "(".to_string(),
+            rp: ")".to_string(),
+            or: ",".to_string(),
+            and: "+".to_string(),
+            not: "^".to_string(),
+        }
+    }
+}
+
 pub trait ExprOps {
     fn lp(&self) -> &str;
     fn rp(&self) -> &str;
@@ -62,11 +76,10 @@ impl ExprOps for Ops {
 }
 
 pub fn parse<S: AsRef<str>>(input: S, ops: &dyn ExprOps) -> Result<Expr, Error> {
-    let parsed = parser::parse_expr(CompleteStr(input.as_ref()), ops)
-        .expect(&format!("Error occurred while parsing expression `{}`", input.as_ref()));
-    match parsed {
+    let invalid_expr = || TodoError::InvalidExpr { value: input.as_ref().to_string() };
+    match parser::parse_expr(CompleteStr(input.as_ref()), ops).map_err(|_| invalid_expr())? {
         (CompleteStr(""), expr) => Ok(expr),
-        (CompleteStr(s), _) => Err(format_err!("Can't parse expression`{}`", s)),
+        _ => Err(invalid_expr().into()),
     }
 }
 
@@ -6,6 +6,7 @@ extern crate regex;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+extern crate serde_json;
 #[macro_use]
 extern crate enum_iterator_derive;
 #[macro_use]
@@ -13,28 +14,58 @@ extern crate failure;
 extern crate walkdir;
 #[macro_use]
 extern crate nom;
+extern crate chrono;
 
-use clap::{App, Arg, SubCommand};
+use std::{env, process};
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use failure::Error;
 use cmd::Cmd;
 use lang::Str;
 use settings::Settings;
+use todo::error::TodoError;
 
 mod cmd;
+mod date;
+mod editor;
 mod expr;
 mod lang;
 mod settings;
 mod todo;
+mod user;
 
 const VERSION: Str = "0.1.0"; // Related with `version` value in Cargo.toml
 const PARAMS_ARG_NAME: Str = "params";
 const PARAM_SEPARATOR: u8 = b':';
+const DRY_RUN_ARG_NAME: Str = "dry-run";
+const DEBUG_ARG_NAME: Str = "debug";
+const EDIT_ARG_NAME: Str = "edit";
+const PURGE_ARG_NAME: Str = "purge";
+const FROM_FORMAT_ARG_NAME: Str = "from-format";
+const TO_FORMAT_ARG_NAME: Str = "to-format";
+const FORMAT_ARG_NAME: Str = "format";
 
 fn main() {
-    let settings = Settings::new().expect("Read settings error");
+    let mut settings = Settings::new().unwrap_or_else(|err| {
+        let debug = env::args().any(|arg| arg == format!("--{}", DEBUG_ARG_NAME));
+        exit_with_error(&err, TodoError::EXIT_CONFIG, debug)
+    });
 
-    let matches = App::new("Todo")
+    let mut app = App::new("Todo")
         .version(VERSION)
         .about("The issue tracking console tool")
+        .arg(
+            Arg::with_name(DRY_RUN_ARG_NAME)
+                .long(DRY_RUN_ARG_NAME)
+                .help("Report what would be changed without writing anything")
+                .global(true),
+        )
+        .arg(
+            Arg::with_name(DEBUG_ARG_NAME)
+                .long(DEBUG_ARG_NAME)
+                .help("Print error backtraces")
+                .global(true),
+        )
         .subcommand(
             SubCommand::with_name(Cmd::NEW.name)
                 .about(Cmd::NEW.desc)
@@ -42,6 +73,12 @@ fn main() {
                     Arg::with_name(PARAMS_ARG_NAME)
                         .multiple(true)
                         .required(true),
+                )
+                .arg(
+                    Arg::with_name(EDIT_ARG_NAME)
+                        .short("e")
+                        .long(EDIT_ARG_NAME)
+                        .help("Open editor to write issue body before creation"),
                 ),
         )
         .arg(
@@ -65,27 +102,98 @@ fn main() {
                 .takes_value(true)
                 .default_value(".*")
                 .multiple(true),
-        )
-        .get_matches();
+        );
+
+    for cmd in Cmd::SUBCOMMANDS {
+        let mut subcommand = SubCommand::with_name(cmd.name)
+            .about(cmd.desc)
+            .setting(AppSettings::AllowLeadingHyphen)
+            .arg(Arg::with_name(PARAMS_ARG_NAME).multiple(true));
+        if cmd.name == Cmd::RM.name {
+            subcommand = subcommand.arg(
+                Arg::with_name(PURGE_ARG_NAME)
+                    .long(PURGE_ARG_NAME)
+                    .help("Delete issue file permanently instead of moving it to trash"),
+            );
+        }
+        if cmd.name == Cmd::MIGRATE.name {
+            subcommand = subcommand
+                .arg(
+                    Arg::with_name(FROM_FORMAT_ARG_NAME)
+                        .long(FROM_FORMAT_ARG_NAME)
+                        .takes_value(true)
+                        .help("Filename format the issues are stored with"),
+                )
+                .arg(
+                    Arg::with_name(TO_FORMAT_ARG_NAME)
+                        .long(TO_FORMAT_ARG_NAME)
+                        .takes_value(true)
+                        .help("Filename format to move the issues to"),
+                );
+        }
+        if cmd.name == Cmd::EXPORT.name || cmd.name == Cmd::IMPORT.name {
+            subcommand = subcommand.arg(
+                Arg::with_name(FORMAT_ARG_NAME)
+                    .long(FORMAT_ARG_NAME)
+                    .takes_value(true)
+                    .help("Exchange format: json (default), todotxt or taskwarrior"),
+            );
+        }
+        app = app.subcommand(subcommand);
+    }
+
+    let matches = app.get_matches();
+    settings.dry_run |= is_flag_present(&matches, DRY_RUN_ARG_NAME);
+    settings.debug |= is_flag_present(&matches, DEBUG_ARG_NAME);
+    if settings.debug {
+        env::set_var("RUST_BACKTRACE", "1");
+    }
 
+    if let Err(err) = run(&matches, &settings) {
+        exit_with_error(&err, TodoError::EXIT_FAILURE, settings.debug);
+    }
+}
+
+fn run(matches: &ArgMatches, settings: &Settings) -> Result<(), Error> {
     if let Some(matches) = matches.subcommand_matches(Cmd::NEW.name) {
-        Cmd::NEW
-            .process(matches, PARAMS_ARG_NAME, &settings)
-            .unwrap();
+        Cmd::NEW.process(matches, PARAMS_ARG_NAME, settings)?;
     } else if let Some(matches) = matches.subcommand_matches(Cmd::LIST.name) {
-        Cmd::LIST
-            .process(matches, PARAMS_ARG_NAME, &settings)
-            .unwrap();
+        Cmd::LIST.process(matches, PARAMS_ARG_NAME, settings)?;
+    } else if let Some(cmd) = Cmd::SUBCOMMANDS
+        .iter()
+        .find(|cmd| matches.subcommand_matches(cmd.name).is_some())
+    {
+        let sub_matches = matches.subcommand_matches(cmd.name).unwrap();
+        cmd.process(sub_matches, PARAMS_ARG_NAME, settings)?;
     } else {
         if matches.occurrences_of(Cmd::NEW.name) > 0 {
-            Cmd::NEW
-                .process(&matches, Cmd::NEW.name, &settings)
-                .unwrap();
+            Cmd::NEW.process(matches, Cmd::NEW.name, settings)?;
         }
         if matches.occurrences_of(Cmd::LIST.name) > 0 {
-            Cmd::LIST
-                .process(&matches, Cmd::LIST.name, &settings)
-                .unwrap();
+            Cmd::LIST.process(matches, Cmd::LIST.name, settings)?;
         }
     }
+    Ok(())
+}
+
+fn is_flag_present(matches: &ArgMatches, name: &str) -> bool {
+    matches.is_present(name)
+        || matches.subcommand().1.map_or(false, |matches| matches.is_present(name))
+}
+
+fn exit_with_error(err: &Error, default_code: i32, debug: bool) -> ! {
+    eprintln!("error: {}", err);
+    for cause in err.iter_causes() {
+        eprintln!("  caused by: {}", cause);
+    }
+    if debug {
+        eprintln!("{:?}", err.backtrace());
+    }
+
+    let code = err.iter_chain()
+        .filter_map(|fail| fail.downcast_ref::<TodoError>())
+        .map(TodoError::exit_code)
+        .next()
+        .unwrap_or(default_code);
+    process::exit(code)
 }
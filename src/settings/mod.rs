@@ -5,14 +5,27 @@ use std::collections::HashMap;
 use std::env;
 
 use config::{Config, Environment, File};
+use date::{self, Calendar};
 use failure::Error;
 use lang::{Str, ToStringsCollect};
+use regex::Regex;
+use todo::command::store::fs::{self, AttrsStyle};
+use todo::error::TodoError;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Issue {
     pub attrs: HashMap<String, Vec<String>>,
     pub attrs_order: Option<Vec<String>>,
+    pub multi_value_attrs: Vec<String>,
     pub id_attr_key: String,
+    pub tags_attr_key: String,
+    pub due_attr_key: String,
+    pub closed_attr_key: String,
+    pub recur_attr_key: String,
+    pub blocks_attr_key: String,
+    pub blocked_by_attr_key: String,
+    pub parent_attr_key: String,
+    pub progress_attr_key: String,
     pub default_attr_key: String,
 }
 
@@ -28,7 +41,16 @@ impl Default for Issue {
         Issue {
             attrs,
             attrs_order: None,
+            multi_value_attrs: vec!["tags".to_string()],
             id_attr_key: "id".to_string(),
+            tags_attr_key: "tags".to_string(),
+            due_attr_key: "due".to_string(),
+            closed_attr_key: "closed".to_string(),
+            recur_attr_key: "recur".to_string(),
+            blocks_attr_key: "blocks".to_string(),
+            blocked_by_attr_key: "blocked_by".to_string(),
+            parent_attr_key: "parent".to_string(),
+            progress_attr_key: "progress".to_string(),
             default_attr_key: "name".to_string(),
         }
     }
@@ -40,8 +62,11 @@ pub struct FsStore {
     pub format: String,
     pub capture: String,
     pub find_all: bool,
+    pub header_only: bool,
+    pub header_limit: Option<usize>,
     pub issues_dir: String,
     pub ext: String,
+    pub attrs_style: String,
     pub id_generator: String,
 }
 
@@ -59,8 +84,11 @@ impl Default for FsStore {
             format: Self::DEFAULT_FORMAT.to_string(),
             capture: Self::DEFAULT_CAPTURE.to_string(),
             find_all: false,
+            header_only: false,
+            header_limit: None,
             issues_dir: "issues".to_string(),
             ext: "md".to_string(),
+            attrs_style: "attr".to_string(),
             id_generator: String::default(),
         }
     }
@@ -101,6 +129,48 @@ impl Default for SequenceGenerator {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TimeTracking {
+    pub log_file: String,
+}
+
+impl Default for TimeTracking {
+    fn default() -> Self {
+        TimeTracking {
+            log_file: "todo.time".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct History {
+    pub file: String,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History {
+            file: "todo.history".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Journal {
+    pub file: String,
+    #[serde(skip)]
+    pub operation: String,
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Journal {
+            file: "todo.journal".to_string(),
+            operation: String::default(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Operation {
     pub lp: String,
@@ -127,24 +197,66 @@ pub struct Cli {
     pub operation: Operation,
 }
 
-#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NewCommand {
     pub default_attrs: Option<HashMap<String, String>>,
+    pub created_attr: Option<String>,
+    pub author_attr: Option<String>,
+    pub updated_attr: Option<String>,
+    pub skeleton: Option<String>,
+    pub templates_dir: Option<String>,
+    pub templates: Option<HashMap<String, String>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CommentCommand {
+    pub section: String,
+}
+
+impl Default for NewCommand {
+    fn default() -> Self {
+        NewCommand {
+            default_attrs: None,
+            created_attr: Some("created".to_string()),
+            author_attr: Some("author".to_string()),
+            updated_attr: Some("updated".to_string()),
+            skeleton: None,
+            templates_dir: None,
+            templates: None,
+        }
+    }
+}
+
+impl Default for CommentCommand {
+    fn default() -> Self {
+        CommentCommand {
+            section: "## Comments".to_string(),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Command {
     pub new: NewCommand,
+    pub comment: CommentCommand,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Settings {
     pub debug: bool,
+    pub dry_run: bool,
+    pub user: Option<String>,
+    pub editor: Option<String>,
+    // A fixed UTC offset; named zones aren't supported, so DST changes are not followed
+    pub timezone: Option<String>,
     pub issue: Issue,
     pub store: Store,
     pub cli: Cli,
     pub command: Command,
     pub generator: Generator,
+    pub time: TimeTracking,
+    pub history: History,
+    pub journal: Journal,
 }
 
 impl Settings {
@@ -169,7 +281,39 @@ impl Settings {
         // Eg.. `TODO_SET_DEBUG=1 ./target/todo` would set the `debug` key
         config.merge(Environment::with_prefix("TODO_SET"))?;
 
-        let settings = config.try_into()?;
+        let mut settings: Settings = config.try_into()?;
+        settings.validate()?;
+        settings.journal.operation = fs::Journal::new_operation();
         Ok(settings)
     }
+
+    pub fn validate(&self) -> Result<(), TodoError> {
+        let fs = &self.store.fs;
+        match fs.id_generator.as_str() {
+            "" | "sequence" => (),
+            name => return Err(TodoError::UnsupportedGenerator { name: name.to_string() }),
+        }
+        if AttrsStyle::by_key(&fs.attrs_style).is_none() {
+            return Err(TodoError::UnsupportedAttrsStyle { style: fs.attrs_style.clone() });
+        }
+        if Regex::new(&fs.capture).is_err() {
+            return Err(TodoError::InvalidRegex { value: fs.capture.clone() });
+        }
+        if let Some(ref timezone) = self.timezone {
+            if date::parse_offset(timezone).is_none() {
+                return Err(TodoError::UnsupportedTimezone { value: timezone.clone() });
+            }
+        }
+        if let Some(ref default_attrs) = self.command.new.default_attrs {
+            let due_key = &self.issue.due_attr_key;
+            let due_aliases = self.issue.attrs.get(due_key);
+            for (key, value) in default_attrs {
+                let is_due = key == due_key || due_aliases.map(|aliases| aliases.contains(key)).unwrap_or(false);
+                if is_due && !value.is_empty() && Calendar::default().parse_date(value).is_none() {
+                    return Err(TodoError::InvalidDate { value: value.clone() });
+                }
+            }
+        }
+        Ok(())
+    }
 }
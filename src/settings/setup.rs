@@ -1,11 +1,64 @@
+use date::{self, Calendar};
+use expr::Ops;
 use settings::{Generator, Settings};
-use todo::command::{store::{fs, Create, Find}, List, New};
+use todo::command::{
+    store::{fs, Create, Find, Update}, Agenda, Check, Close, Comment, Export, History, Import, Link, List, Log,
+    New, Report, Show, Start, Stop, Tag, Tags, Tree,
+};
+use todo::history::HistoryLog;
 use todo::issue::{Content, Issue};
+use todo::timelog::TimeLog;
+use editor;
+use user;
 
 pub trait Setup {
     fn setup(self, settings: &Settings) -> Self;
 }
 
+// An empty key turns the stamp off
+fn stamp_attr(key: &Option<String>) -> Option<String> {
+    key.clone().filter(|key| !key.is_empty())
+}
+
+impl Setup for Calendar {
+    fn setup(mut self, settings: &Settings) -> Self {
+        if let Some(ref timezone) = settings.timezone {
+            self.offset = date::parse_offset(timezone);
+        }
+        self
+    }
+}
+
+impl Setup for TimeLog {
+    fn setup(mut self, settings: &Settings) -> Self {
+        let log_file = &settings.time.log_file;
+        self.file = if log_file.is_empty() { None } else { Some(log_file.clone()) };
+        self.dry_run = settings.dry_run;
+        self.journal = fs::Journal::default().setup(settings);
+        self
+    }
+}
+
+impl Setup for HistoryLog {
+    fn setup(mut self, settings: &Settings) -> Self {
+        let file = &settings.history.file;
+        self.file = if file.is_empty() { None } else { Some(file.clone()) };
+        self.journal = fs::Journal::default().setup(settings);
+        self
+    }
+}
+
+impl Setup for fs::Journal {
+    fn setup(mut self, settings: &Settings) -> Self {
+        let file = &settings.journal.file;
+        self.file = if file.is_empty() { None } else { Some(file.clone()) };
+        self.timestamp = Calendar::default().setup(settings).timestamp();
+        self.operation = settings.journal.operation.clone();
+        self.dry_run = settings.dry_run;
+        self
+    }
+}
+
 impl<T> Setup for Issue<T>
 where
     T: Content,
@@ -33,8 +86,27 @@ where
                 .add_aliases(key.as_str(), aliases)
                 .expect("Setup unordered aliases error");
         }
+        for attr in &settings.issue.multi_value_attrs {
+            self.attrs.add_multi_value_key(attr.as_str());
+        }
         let key = self.attrs.add_key(&settings.issue.id_attr_key);
         self.id_attr_key = (*key).clone();
+        let key = self.attrs.add_key(&settings.issue.tags_attr_key);
+        self.tags_attr_key = (*key).clone();
+        let key = self.attrs.add_key(&settings.issue.due_attr_key);
+        self.due_attr_key = (*key).clone();
+        let key = self.attrs.add_key(&settings.issue.closed_attr_key);
+        self.closed_attr_key = (*key).clone();
+        let key = self.attrs.add_key(&settings.issue.recur_attr_key);
+        self.recur_attr_key = (*key).clone();
+        let key = self.attrs.add_multi_value_key(&settings.issue.blocks_attr_key);
+        self.blocks_attr_key = (*key).clone();
+        let key = self.attrs.add_multi_value_key(&settings.issue.blocked_by_attr_key);
+        self.blocked_by_attr_key = (*key).clone();
+        let key = self.attrs.add_key(&settings.issue.parent_attr_key);
+        self.parent_attr_key = (*key).clone();
+        let key = self.attrs.add_key(&settings.issue.progress_attr_key);
+        self.progress_attr_key = (*key).clone();
         let key = self.attrs.add_key(&settings.issue.default_attr_key);
         self.attrs.default_key = key;
         self
@@ -59,6 +131,23 @@ impl Setup for fs::Create {
             settings.store.fs.ext.clone()
         );
 
+        self.attrs.set_attr_value(
+            fs::CreateAttr::AttrsStyle.key(),
+            settings.store.fs.attrs_style.clone()
+        );
+
+        self.attrs.set_attr_value(
+            fs::CreateAttr::Edit.key(),
+            false.to_string()
+        );
+
+        self.attrs.set_attr_value(fs::CreateAttr::Template.key(), "");
+        self.templates_dir = settings.command.new.templates_dir.clone()
+            .unwrap_or_else(|| "templates".to_string());
+        self.templates = settings.command.new.templates.clone().unwrap_or_default();
+        self.editor = editor::editor_command(settings.editor.as_ref());
+        self.skeleton = settings.command.new.skeleton.clone().unwrap_or_else(|| "\n".to_string());
+
         for (key, aliases) in &settings.store.fs.attrs {
             let _ = self.attrs.add_aliases(key.as_str(), aliases);
         }
@@ -68,11 +157,14 @@ impl Setup for fs::Create {
                 self.id_generator = Some(fs::SequenceGenerator {
                     required: settings.generator.sequence.required,
                     file: Some(settings.generator.sequence.file.clone()),
+                    journal: fs::Journal::default().setup(settings),
+                    dry_run: settings.dry_run,
                 })
             }
-            "" => self.id_generator = None,
-            generator => panic!("Unsupported generator type `{}`", generator),
+            _ => self.id_generator = None,
         }
+        self.journal = fs::Journal::default().setup(settings);
+        self.dry_run = settings.dry_run;
         self
     }
 }
@@ -87,6 +179,20 @@ impl Setup for fs::Find {
             );
         }
 
+        if settings.store.fs.header_only {
+            self.attrs.set_attr_value(
+                fs::FindAttr::HeaderOnly.key(),
+                true.to_string()
+            );
+        }
+
+        if let Some(header_limit) = settings.store.fs.header_limit {
+            self.attrs.set_attr_value(
+                fs::FindAttr::HeaderLimit.key(),
+                header_limit.to_string()
+            );
+        }
+
         self.attrs.set_attr_value(
             fs::FindAttr::IssuesDir.key(),
             settings.store.fs.issues_dir.clone(),
@@ -100,6 +206,35 @@ impl Setup for fs::Find {
         for (key, aliases) in &settings.store.fs.attrs {
             let _ = self.attrs.add_aliases(key.as_str(), aliases);
         }
+
+        self.calendar = Calendar::default().setup(settings);
+
+        let operation = &settings.cli.operation;
+        self.ops = Ops {
+            lp: operation.lp.clone(),
+            rp: operation.rp.clone(),
+            or: operation.or.clone(),
+            and: operation.and.clone(),
+            not: operation.not.clone(),
+        };
+        self
+    }
+}
+
+impl Setup for fs::Update {
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.attrs.set_attr_value(
+            fs::UpdateAttr::AttrsStyle.key(),
+            settings.store.fs.attrs_style.clone()
+        );
+        self.updated_attr = stamp_attr(&settings.command.new.updated_attr);
+        self.calendar = Calendar::default().setup(settings);
+        self.history = HistoryLog::default().setup(settings);
+        self.user = user::user_name(settings.user.as_ref());
+        self.journal = fs::Journal::default().setup(settings);
+        self.dry_run = settings.dry_run;
+
+        self.find = self.find.setup(settings);
         self
     }
 }
@@ -109,16 +244,48 @@ where
     T: Create,
 {
     fn setup(mut self, settings: &Settings) -> Self {
+        self.calendar = Calendar::default().setup(settings);
+
         let command = &settings.command;
+        self.created_attr = stamp_attr(&command.new.created_attr);
+        self.updated_attr = stamp_attr(&command.new.updated_attr);
+        self.author_attr = stamp_attr(&command.new.author_attr);
+        if self.author_attr.is_some() {
+            self.author = user::user_name(settings.user.as_ref());
+        }
+
         if let Some(ref default_attrs) = command.new.default_attrs {
             for (key, value) in default_attrs.iter() {
-                self.issue.attrs.set_attr_value(key.as_str(), value.clone());
+                let value = self.normalize_attr_value(key.as_str(), value.clone())
+                    .unwrap_or_else(|_| value.clone());
+                self.issue.attrs.set_attr_value(key.as_str(), value);
             }
         }
         self
     }
 }
 
+impl<T> Setup for Export<T>
+where
+    T: Find,
+{
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.comment_section = settings.command.comment.section.clone();
+        self.calendar = Calendar::default().setup(settings);
+        self
+    }
+}
+
+impl<T> Setup for Import<T>
+where
+    T: Create + Clone,
+{
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.comment_section = settings.command.comment.section.clone();
+        self
+    }
+}
+
 impl<T> Setup for List<T>
 where
     T: Find,
@@ -127,3 +294,196 @@ where
         self
     }
 }
+
+impl<T> Setup for Tag<T>
+where
+    T: Update,
+{
+    fn setup(self, _settings: &Settings) -> Self {
+        self
+    }
+}
+
+impl<T> Setup for Tags<T>
+where
+    T: Find,
+{
+    fn setup(self, _settings: &Settings) -> Self {
+        self
+    }
+}
+
+impl<T> Setup for Agenda<T>
+where
+    T: Find,
+{
+    fn setup(self, _settings: &Settings) -> Self {
+        self
+    }
+}
+
+impl<U, C> Setup for Close<U, C>
+where
+    U: Update,
+    C: Create + Clone,
+{
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.calendar = Calendar::default().setup(settings);
+        self.comment_section = settings.command.comment.section.clone();
+        self
+    }
+}
+
+impl<F, U> Setup for Link<F, U>
+where
+    F: Find,
+    U: Update + Clone,
+{
+    fn setup(self, _settings: &Settings) -> Self {
+        self
+    }
+}
+
+impl<T> Setup for Show<T>
+where
+    T: Find,
+{
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.comments_section = settings.command.comment.section.clone();
+        self
+    }
+}
+
+impl<T> Setup for Tree<T>
+where
+    T: Find,
+{
+    fn setup(self, _settings: &Settings) -> Self {
+        self
+    }
+}
+
+impl<T> Setup for Check<T>
+where
+    T: Update,
+{
+    fn setup(self, _settings: &Settings) -> Self {
+        self
+    }
+}
+
+impl<T> Setup for Start<T>
+where
+    T: Find,
+{
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.time_log = TimeLog::default().setup(settings);
+        self.calendar = Calendar::default().setup(settings);
+        self
+    }
+}
+
+impl<T> Setup for Log<T>
+where
+    T: Find,
+{
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.time_log = TimeLog::default().setup(settings);
+        self.calendar = Calendar::default().setup(settings);
+        self
+    }
+}
+
+impl<T> Setup for Report<T>
+where
+    T: Find,
+{
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.time_log = TimeLog::default().setup(settings);
+        self.calendar = Calendar::default().setup(settings);
+        self
+    }
+}
+
+impl Setup for Stop {
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.time_log = TimeLog::default().setup(settings);
+        self.calendar = Calendar::default().setup(settings);
+        self
+    }
+}
+
+impl<T> Setup for Comment<T>
+where
+    T: Update,
+{
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.author = user::user_name(settings.user.as_ref());
+        self.section = settings.command.comment.section.clone();
+        self.calendar = Calendar::default().setup(settings);
+        self
+    }
+}
+
+impl<T> Setup for History<T>
+where
+    T: Find,
+{
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.history = HistoryLog::default().setup(settings);
+        self
+    }
+}
+
+impl Setup for fs::Undo {
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.journal = fs::Journal::default().setup(settings);
+        self
+    }
+}
+
+impl Setup for fs::Edit {
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.editor = editor::editor_command(settings.editor.as_ref());
+        self.ext = settings.store.fs.ext.clone();
+        self.calendar = Calendar::default().setup(settings);
+        self
+    }
+}
+
+impl Setup for fs::Move {
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.format = settings.store.fs.format.clone();
+        self.calendar = Calendar::default().setup(settings);
+        self.history = HistoryLog::default().setup(settings);
+        self.user = user::user_name(settings.user.as_ref());
+        self.journal = fs::Journal::default().setup(settings);
+        self.dry_run = settings.dry_run;
+        self
+    }
+}
+
+impl Setup for fs::Remove {
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.journal = fs::Journal::default().setup(settings);
+        self.dry_run = settings.dry_run;
+        self
+    }
+}
+
+impl Setup for fs::Restore {
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.journal = fs::Journal::default().setup(settings);
+        self.dry_run = settings.dry_run;
+        self
+    }
+}
+
+impl Setup for fs::Migrate {
+    fn setup(mut self, settings: &Settings) -> Self {
+        self.attrs_style = settings.store.fs.attrs_style.clone();
+        self.journal = fs::Journal::default().setup(settings);
+        self.dry_run = settings.dry_run;
+        self
+    }
+}
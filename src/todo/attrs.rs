@@ -9,11 +9,14 @@ pub struct Attrs {
     attrs: HashMap<String, String>,
     aliases: HashMap<String, Rc<String>>,
     keep_order_keys: Vec<Rc<String>>,
+    multi_value_keys: Vec<Rc<String>>,
     pub keys: Vec<Rc<String>>,
     pub default_key: Rc<String>,
 }
 
 impl Attrs {
+    pub const VALUES_SEPARATOR: char = ',';
+
     #[inline]
     pub fn is_keep_order_key(&self, key: &Rc<String>) -> bool {
         self.keep_order_keys.contains(key)
@@ -31,6 +34,34 @@ impl Attrs {
         key
     }
 
+    #[inline]
+    pub fn is_multi_value_key(&self, key: &str) -> bool {
+        self.multi_value_keys.iter().any(|item| item.as_str() == key)
+    }
+
+    pub fn add_multi_value_key(&mut self, key: &str) -> Rc<String> {
+        let key = self.add_key(key);
+        if !self.is_multi_value_key(key.as_str()) {
+            self.multi_value_keys.push(key.clone())
+        }
+        key
+    }
+
+    pub fn split_values(value: &str) -> Vec<String> {
+        value.split(Self::VALUES_SEPARATOR)
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty())
+            .map(String::from)
+            .collect()
+    }
+
+    pub fn join_values<S: AsRef<str>>(values: &[S]) -> String {
+        values.iter()
+            .map(|item| item.as_ref())
+            .collect::<Vec<_>>()
+            .join(&format!("{} ", Self::VALUES_SEPARATOR))
+    }
+
     pub fn add_key(&mut self, key: &str) -> Rc<String> {
         self.find_key(key).unwrap_or_else(|| {
             let key = Rc::new(key.to_string());
@@ -71,6 +102,10 @@ impl Attrs {
         }
     }
 
+    pub fn clear_values(&mut self) {
+        self.attrs.clear();
+    }
+
     pub fn count(&self) -> usize {
         self.attrs.len()
     }
@@ -87,6 +122,14 @@ impl Attrs {
         self.attrs.get(key).map(|s| s.as_str()).unwrap_or_default()
     }
 
+    pub fn attr_values(&self, key: &str) -> Vec<String> {
+        match self.attrs.get(key) {
+            Some(value) if self.is_multi_value_key(key) => Attrs::split_values(value),
+            Some(value) if !value.is_empty() => vec![value.clone()],
+            _ => vec![],
+        }
+    }
+
     pub fn set_attr_value<V: Into<String>>(&mut self, alias: &str, value: V) -> Option<String> {
         let mut exist_key = false;
         let key = self.key_by_alias(alias)
@@ -163,4 +206,21 @@ mod tests {
             assert_eq!(results[i], (key, value));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn multi_value_keys() {
+        let mut attrs = Attrs::default();
+
+        attrs.add_multi_value_key("tags");
+
+        attrs.set_attr_value("tags", "a, b,,c ");
+        attrs.set_attr_value("key", "a, b");
+
+        assert!(attrs.is_multi_value_key("tags"));
+        assert!(!attrs.is_multi_value_key("key"));
+        assert_eq!(vec!["a", "b", "c"], attrs.attr_values("tags"));
+        assert_eq!(vec!["a, b"], attrs.attr_values("key"));
+        assert!(attrs.attr_values("other").is_empty());
+        assert_eq!("a, b, c", Attrs::join_values(&attrs.attr_values("tags")));
+    }
+}
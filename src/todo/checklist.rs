@@ -0,0 +1,136 @@
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChecklistItem {
+    pub mark_pos: usize,
+    pub checked: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProgressFilter {
+    None,
+    Open,
+    Done,
+    Exact(usize, usize),
+}
+
+impl ProgressFilter {
+    pub fn parse<S: AsRef<str>>(input: S) -> Option<Self> {
+        Some(match input.as_ref().trim() {
+            "" | "none" => ProgressFilter::None,
+            "open" => ProgressFilter::Open,
+            "done" => ProgressFilter::Done,
+            input => {
+                let pos = input.find('/')?;
+                ProgressFilter::Exact(input[..pos].parse().ok()?, input[(pos + 1)..].parse().ok()?)
+            }
+        })
+    }
+
+    pub fn is_match(&self, progress: Option<(usize, usize)>) -> bool {
+        match (self, progress) {
+            (&ProgressFilter::None, None) => true,
+            (_, None) | (&ProgressFilter::None, _) => false,
+            (&ProgressFilter::Open, Some((done, total))) => done < total,
+            (&ProgressFilter::Done, Some((done, total))) => done == total,
+            (&ProgressFilter::Exact(expected_done, expected_total), Some((done, total))) =>
+                done == expected_done && total == expected_total,
+        }
+    }
+}
+
+pub fn checklist_items(text: &str) -> Vec<ChecklistItem> {
+    let mut items = Vec::new();
+    let mut in_code = false;
+    let mut line_start = 0;
+
+    for line in text.split('\n') {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+        } else if !in_code {
+            let bullet_len = if trimmed.starts_with("- ") || trimmed.starts_with("* ") || trimmed.starts_with("+ ") {
+                2
+            } else {
+                let digits = trimmed.chars().take_while(|ch| ch.is_digit(10)).count();
+                if digits > 0 && trimmed[digits..].starts_with(". ") { digits + 2 } else { 0 }
+            };
+            if bullet_len > 0 {
+                let rest = trimmed[bullet_len..].as_bytes();
+                if rest.len() >= 3 && rest[0] == b'[' && rest[2] == b']' {
+                    let mark = rest[1];
+                    if mark == b' ' || mark == b'x' || mark == b'X' {
+                        items.push(ChecklistItem {
+                            mark_pos: line_start + indent + bullet_len + 1,
+                            checked: mark != b' ',
+                        });
+                    }
+                }
+            }
+        }
+        line_start += line.len() + 1;
+    }
+    items
+}
+
+pub fn checklist_progress(text: &str) -> Option<(usize, usize)> {
+    let items = checklist_items(text);
+    if items.is_empty() {
+        None
+    } else {
+        Some((items.iter().filter(|item| item.checked).count(), items.len()))
+    }
+}
+
+pub fn check_item(text: &mut String, number: usize, checked: bool) -> bool {
+    let items = checklist_items(text);
+    match number.checked_sub(1).and_then(|i| items.get(i)) {
+        Some(item) => {
+            let mark = if checked { "x" } else { " " };
+            text.replace_range(item.mark_pos..(item.mark_pos + 1), mark);
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "#[id: 1]\n- [ ] one\n- [x] two\n  * [X] three\n1. [ ] four\n```\n- [ ] code\n```\n- [] no\n";
+
+    #[test]
+    fn checklist_progress() {
+        assert_eq!(Some((2, 4)), super::checklist_progress(TEXT));
+        assert_eq!(None, super::checklist_progress("text\n- item\n"));
+        assert_eq!(Some((1, 1)), super::checklist_progress("- [✓] done\n- [é]\n- [x] ok\n"));
+    }
+
+    #[test]
+    fn check_item() {
+        let mut text = TEXT.to_string();
+        assert!(super::check_item(&mut text, 1, true));
+        assert!(super::check_item(&mut text, 3, false));
+        assert!(super::check_item(&mut text, 4, true));
+        assert!(!super::check_item(&mut text, 5, true));
+        assert!(!super::check_item(&mut text, 0, true));
+        assert_eq!(
+            "#[id: 1]\n- [x] one\n- [x] two\n  * [ ] three\n1. [x] four\n```\n- [ ] code\n```\n- [] no\n",
+            text
+        );
+    }
+
+    #[test]
+    fn progress_filter() {
+        let filter = |input| ProgressFilter::parse(input).unwrap();
+
+        assert!(filter("").is_match(None));
+        assert!(!filter("none").is_match(Some((0, 1))));
+        assert!(filter("open").is_match(Some((1, 2))));
+        assert!(!filter("open").is_match(Some((2, 2))));
+        assert!(filter("done").is_match(Some((2, 2))));
+        assert!(filter("3/5").is_match(Some((3, 5))));
+        assert!(!filter("3/5").is_match(Some((2, 5))));
+        assert_eq!(None, ProgressFilter::parse("half"));
+    }
+}
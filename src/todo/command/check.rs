@@ -0,0 +1,87 @@
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use todo::attrs::Attrs;
+use todo::checklist;
+use todo::command::store::Update;
+use todo::command::Command;
+use todo::error::TodoError;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Check<T>
+where
+    T: Update,
+{
+    pub update: Option<T>,
+    pub issue: Issue<String>,
+    pub items: Vec<(usize, bool)>,
+}
+
+impl<T> Check<T>
+where
+    T: Update,
+{
+    fn add_items(&mut self, value: &str, checked: bool) -> Result<(), TodoError> {
+        for item in Attrs::split_values(value) {
+            let number = item.parse()
+                .map_err(|_| TodoError::UnknownCommandParam { param: item.clone() })?;
+            self.items.push((number, checked));
+        }
+        Ok(())
+    }
+}
+
+impl<T> Command for Check<T>
+where
+    T: Update,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            let mut is_update_param = false;
+            if let Some(update) = self.update.as_mut() {
+                is_update_param = update.set_param(param, value.clone()).is_ok();
+            }
+            if !is_update_param {
+                self.issue.attrs.set_attr_value(param.to_lowercase().as_str(), value);
+            }
+        } else if value.starts_with('+') {
+            self.add_items(&value[1..], true)?;
+        } else if value.starts_with('-') {
+            self.add_items(&value[1..], false)?;
+        } else if self.issue.get_id().is_none() {
+            let id_attr_key = self.issue.id_attr_key.clone();
+            self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        } else {
+            self.add_items(&value, true)?;
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut update = mem::replace(&mut self.update, None)
+            .expect("Update command not exist");
+
+        self.issue.required_id()?;
+        update.init_from(&self.issue)?;
+
+        let items = &self.items;
+        update.update(|issue| {
+            let mut content = issue.content.clone().unwrap_or_default();
+            for &(number, checked) in items {
+                if !checklist::check_item(&mut content, number, checked) {
+                    return Err(TodoError::ChecklistItemNotFound { number }.into());
+                }
+            }
+            issue.content = Some(content);
+            Ok(())
+        })?;
+        self.update = Some(update);
+        Ok(())
+    }
+}
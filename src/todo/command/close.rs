@@ -0,0 +1,141 @@
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use chrono::NaiveDate;
+
+use date::{self, Calendar, Recurrence};
+use todo::command::store::{Create, Update};
+use todo::command::{Command, New};
+use todo::comments;
+use todo::error::TodoError;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Close<U, C>
+where
+    U: Update,
+    C: Create + Clone,
+{
+    pub update: Option<U>,
+    pub new: Option<New<C>>,
+    pub issue: Issue<String>,
+    pub calendar: Calendar,
+    pub comment_section: String,
+}
+
+impl<U, C> Close<U, C>
+where
+    U: Update,
+    C: Create + Clone,
+{
+    pub fn next_due(&self, issue: &Issue<String>) -> Result<Option<String>, TodoError> {
+        let recur = issue.attrs.attr_value_as_str(&issue.recur_attr_key).trim();
+        if recur.is_empty() {
+            return Ok(None);
+        }
+
+        let invalid_recurrence = || TodoError::InvalidRecurrence { value: recur.to_string() };
+        let base = NaiveDate::parse_from_str(issue.attrs.attr_value_as_str(&issue.due_attr_key), date::DATE_FORMAT)
+            .unwrap_or_else(|_| self.calendar.today());
+        let due = Recurrence::parse(recur)
+            .and_then(|recurrence| recurrence.next_after(base))
+            .ok_or_else(invalid_recurrence)?;
+        Ok(Some(date::format_date(due)))
+    }
+
+    fn next_body(&self, update: &U, issue: &Issue<String>) -> Result<Option<String>, Error> {
+        let record = update.issue_record(issue)?;
+        let body = record.body.as_ref().map(String::as_str).unwrap_or("");
+        let (body, _) = comments::split_comments(body, &self.comment_section);
+        let body = body.trim_end();
+        Ok(if body.is_empty() { None } else { Some(format!("{}\n", body)) })
+    }
+
+    fn create_next(&self, template: &New<C>, issue: &Issue<String>, due: &str, body: Option<String>) -> Result<(), Error> {
+        let mut new = template.clone();
+        let skip_keys = [
+            Some(&issue.id_attr_key),
+            Some(&issue.closed_attr_key),
+            template.created_attr.as_ref(),
+            template.updated_attr.as_ref(),
+            template.author_attr.as_ref(),
+        ];
+
+        for key in &issue.attrs.keys {
+            let value = if **key == issue.due_attr_key {
+                due
+            } else {
+                issue.attrs.attr_value_as_str(key.as_str())
+            };
+            if !value.is_empty() && !skip_keys.contains(&Some(&**key)) {
+                new.set_param(key.as_str(), value.to_string())?;
+            }
+        }
+        new.issue.content = body;
+        new.exec()
+    }
+}
+
+impl<U, C> Command for Close<U, C>
+where
+    U: Update,
+    C: Create + Clone,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            let mut is_update_param = false;
+            if let Some(update) = self.update.as_mut() {
+                is_update_param = update.set_param(param, value.clone()).is_ok();
+            }
+            if !is_update_param {
+                self.issue.attrs.set_attr_value(param.to_lowercase().as_str(), value);
+            }
+        } else {
+            let id_attr_key = self.issue.id_attr_key.clone();
+            self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut update = mem::replace(&mut self.update, None)
+            .expect("Update command not exist");
+        let new = mem::replace(&mut self.new, None)
+            .expect("New command not exist");
+
+        self.issue.required_id()?;
+        update.init_from(&self.issue)?;
+
+        let closed = date::format_date(self.calendar.today());
+        let generates_id = new.create.as_ref().map(Create::generates_id).unwrap_or(false);
+        let mut next_issues = Vec::new();
+        update.update(|issue| {
+            if issue.is_closed() {
+                return Ok(());
+            }
+            if let Some(due) = self.next_due(issue)? {
+                // The closed issue id must not be reused, so a recurring issue with id needs a fresh one
+                if let Some(id) = issue.get_id().filter(|_| !generates_id) {
+                    return Err(TodoError::IdGeneratorRequired { id: id.clone() }.into());
+                }
+                next_issues.push((issue.clone(), due));
+            }
+            let closed_attr_key = issue.closed_attr_key.clone();
+            issue.attrs.set_attr_value(closed_attr_key.as_str(), closed.clone());
+            Ok(())
+        })?;
+
+        for &(ref issue, ref due) in &next_issues {
+            let body = self.next_body(&update, issue)?;
+            self.create_next(&new, issue, due, body)?;
+        }
+        self.update = Some(update);
+        self.new = Some(new);
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use date::Calendar;
+use todo::command::store::Update;
+use todo::command::Command;
+use todo::comments::{self, Comment as IssueComment};
+use todo::error::TodoError;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Comment<T>
+where
+    T: Update,
+{
+    pub update: Option<T>,
+    pub issue: Issue<String>,
+    pub text: Vec<String>,
+    pub author: Option<String>,
+    pub section: String,
+    pub calendar: Calendar,
+}
+
+impl<T> Comment<T>
+where
+    T: Update,
+{
+    pub const ANONYMOUS: &'static str = "anonymous";
+}
+
+impl<T> Command for Comment<T>
+where
+    T: Update,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            let mut is_update_param = false;
+            if let Some(update) = self.update.as_mut() {
+                is_update_param = update.set_param(param, value.clone()).is_ok();
+            }
+            if !is_update_param {
+                self.issue.attrs.set_attr_value(param.to_lowercase().as_str(), value);
+            }
+        } else if self.issue.get_id().is_none() {
+            let id_attr_key = self.issue.id_attr_key.clone();
+            self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        } else {
+            self.text.push(value);
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut update = mem::replace(&mut self.update, None)
+            .expect("Update command not exist");
+
+        self.issue.required_id()?;
+        let comment = IssueComment {
+            timestamp: self.calendar.timestamp(),
+            author: self.author.clone().unwrap_or_else(|| Self::ANONYMOUS.to_string()),
+            text: self.text.join(" "),
+        };
+        if comment.text.trim().is_empty() {
+            return Err(TodoError::ParamNotSpecified { param: "text".to_string() }.into());
+        }
+        update.init_from(&self.issue)?;
+
+        let section = self.section.as_str();
+        update.update(|issue| {
+            let mut content = issue.content.clone().unwrap_or_default();
+            comments::append_comment(&mut content, section, &comment);
+            issue.content = Some(content);
+            Ok(())
+        })?;
+        self.update = Some(update);
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use date::Calendar;
+use todo::command::store::Find;
+use todo::command::Command;
+use todo::error::TodoError;
+use todo::exchange::ExchangeFormat;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Export<T>
+where
+    T: Find,
+{
+    pub find: Option<T>,
+    pub issue: Issue<String>,
+    pub format: String,
+    pub comment_section: String,
+    pub calendar: Calendar,
+}
+
+impl<T> Export<T>
+where
+    T: Find,
+{
+    pub const FORMAT_PARAM: &'static str = "format";
+}
+
+impl<T> Command for Export<T>
+where
+    T: Find,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if param == Self::FORMAT_PARAM {
+            self.format = value;
+        } else if !param.is_empty() {
+            let mut is_find_param = false;
+            if let Some(find) = self.find.as_mut() {
+                is_find_param = find.set_param(param, value.clone()).is_ok();
+            }
+            if !is_find_param {
+                self.issue.attrs.set_attr_value(param.to_lowercase().as_str(), value);
+            }
+        } else if let Some(find) = self.find.as_mut() {
+            let default_key = find.default_param_key().to_string();
+            find.set_param(&default_key, value)?;
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.find
+            .as_ref()
+            .map(|find| find.default_param_key())
+            .expect("Find command not exist")
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        let format = ExchangeFormat::parse(&self.format)?;
+        find.init_from(&self.issue)?;
+        let mut records = Vec::new();
+        for issue in find.find_issues()? {
+            records.push(find.issue_record(&issue)?);
+        }
+        println!("{}", format.write_records(&records, &self.issue, &self.comment_section, &self.calendar)?);
+        self.find = Some(find);
+        Ok(())
+    }
+}
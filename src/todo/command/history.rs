@@ -0,0 +1,62 @@
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use todo::command::store::Find;
+use todo::command::Command;
+use todo::error::TodoError;
+use todo::history::HistoryLog;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct History<T>
+where
+    T: Find,
+{
+    pub find: Option<T>,
+    pub issue: Issue<String>,
+    pub history: HistoryLog,
+}
+
+impl<T> Command for History<T>
+where
+    T: Find,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            return Err(TodoError::UnknownCommandParam { param: param.to_string() });
+        }
+        let id_attr_key = self.issue.id_attr_key.clone();
+        self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        let id = self.issue.required_id()?.clone();
+        find.init_from(&self.issue)?;
+        find.find_issues()?
+            .first()
+            .ok_or(TodoError::IssueNotFound { id: id.clone() })?;
+
+        let empty_or = |value: &str| if value.is_empty() { "-".to_string() } else { value.to_string() };
+        for record in self.history.read_records(&id)? {
+            println!(
+                "{} {} {}: {} -> {}",
+                record.timestamp,
+                empty_or(&record.user),
+                record.key,
+                empty_or(&record.old),
+                empty_or(&record.new)
+            );
+        }
+        self.find = Some(find);
+        Ok(())
+    }
+}
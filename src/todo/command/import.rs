@@ -0,0 +1,104 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::result::Result;
+use failure::{Error, ResultExt};
+
+use todo::command::store::Create;
+use todo::command::{Command, New};
+use todo::error::TodoError;
+use todo::exchange::{ExchangeFormat, IssueRecord};
+
+#[derive(Clone, Debug, Default)]
+pub struct Import<T>
+where
+    T: Create + Clone,
+{
+    pub new: Option<New<T>>,
+    pub source: Option<String>,
+    pub format: String,
+    pub comment_section: String,
+}
+
+impl<T> Import<T>
+where
+    T: Create + Clone,
+{
+    pub const STDIN_SOURCE: &'static str = "-";
+    pub const FORMAT_PARAM: &'static str = "format";
+
+    pub fn read_source(&self) -> Result<String, Error> {
+        let mut text = String::new();
+        match self.source {
+            Some(ref path) if path != Self::STDIN_SOURCE => {
+                text = fs::read_to_string(path).context(format!("Can't read import file: {}", path))?;
+            }
+            _ => {
+                io::stdin().read_to_string(&mut text)?;
+            }
+        }
+        Ok(text)
+    }
+
+    fn check_targets(&self, template: &New<T>, records: &[IssueRecord]) -> Result<(), Error> {
+        let create = template.create.as_ref().expect("Create command not exist");
+        let mut paths = Vec::new();
+        for record in records {
+            if let Some(path) = create.target_path(&record.to_issue(&template.issue))? {
+                // Paths of records without id depend on ids generated later, so only disk state is checked
+                if Path::new(&path).exists() || (record.id.is_some() && paths.contains(&path)) {
+                    return Err(TodoError::FileAlreadyExists { path }.into());
+                }
+                paths.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    fn reserve_ids(&self, template: &mut New<T>, records: &[IssueRecord]) -> Result<(), Error> {
+        let max_id = records.iter()
+            .filter_map(|record| record.id.as_ref().and_then(|id| id.parse::<u64>().ok()))
+            .max();
+        if let (Some(create), Some(id)) = (template.create.as_mut(), max_id) {
+            create.reserve_id(&id.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Command for Import<T>
+where
+    T: Create + Clone,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if param == Self::FORMAT_PARAM {
+            self.format = value;
+        } else if !param.is_empty() {
+            return Err(TodoError::UnknownCommandParam { param: param.to_string() });
+        } else {
+            self.source = Some(value);
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        ""
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut template = self.new.clone().expect("New command not exist");
+
+        let format = ExchangeFormat::parse(&self.format)?;
+        let records = format.read_records(&self.read_source()?, &template.issue, &self.comment_section, &template.calendar)
+            .context("Can't parse imported issues")?;
+        self.check_targets(&template, &records)?;
+        self.reserve_ids(&mut template, &records)?;
+
+        for record in &records {
+            let mut new = template.clone();
+            new.issue = record.to_issue(&template.issue);
+            new.exec()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use todo::attrs::Attrs;
+use todo::command::store::{Find, Update};
+use todo::command::Command;
+use todo::dependency::Dependencies;
+use todo::error::TodoError;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Link<F, U>
+where
+    F: Find,
+    U: Update + Clone,
+{
+    pub find: Option<F>,
+    pub update: Option<U>,
+    pub issue: Issue<String>,
+    pub args: Vec<String>,
+}
+
+impl<F, U> Link<F, U>
+where
+    F: Find,
+    U: Update + Clone,
+{
+    pub fn link_ids(&self) -> Result<(String, String), TodoError> {
+        match self.args.as_slice() {
+            [from, relation, to] if *relation == self.issue.blocks_attr_key =>
+                Ok((from.clone(), to.clone())),
+            [from, relation, to] if *relation == self.issue.blocked_by_attr_key =>
+                Ok((to.clone(), from.clone())),
+            [_, relation, _] => Err(TodoError::UnknownCommandParam { param: relation.clone() }),
+            _ => Err(TodoError::UnknownCommandParam { param: self.args.join(" ") }),
+        }
+    }
+
+    pub fn check_link(&self, deps: &Dependencies, blocker: &str, blocked: &str) -> Result<(), TodoError> {
+        for id in &[blocker, blocked] {
+            if !deps.contains(id) {
+                return Err(TodoError::IssueNotFound { id: id.to_string() });
+            }
+        }
+        if blocker == blocked || deps.is_blocked_by(blocker, blocked) {
+            return Err(TodoError::DependencyCycle { from: blocker.to_string(), to: blocked.to_string() });
+        }
+        Ok(())
+    }
+
+    fn add_value(&self, update: &U, id: &str, key: &str, value: &str) -> Result<usize, Error> {
+        let mut update = update.clone();
+        let mut issue = self.issue.clone();
+        let id_attr_key = issue.id_attr_key.clone();
+        issue.attrs.set_attr_value(id_attr_key.as_str(), id);
+        update.init_from(&issue)?;
+
+        update.update(|issue| {
+            let mut values = issue.get_values(key);
+            if !values.iter().any(|item| item == value) {
+                values.push(value.to_string());
+            }
+            issue.attrs.set_attr_value(key, Attrs::join_values(&values));
+            Ok(())
+        })
+    }
+}
+
+impl<F, U> Command for Link<F, U>
+where
+    F: Find,
+    U: Update + Clone,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            let mut is_update_param = false;
+            if let Some(update) = self.update.as_mut() {
+                is_update_param = update.set_param(param, value.clone()).is_ok();
+            }
+            if !is_update_param {
+                return Err(TodoError::UnknownCommandParam { param: param.to_string() });
+            }
+        } else {
+            self.args.push(value);
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+        let update = mem::replace(&mut self.update, None)
+            .expect("Update command not exist");
+
+        let (blocker, blocked) = self.link_ids()?;
+        find.init_from(&self.issue)?;
+        let issues = find.find_issues()?;
+        let deps = Dependencies::from_issues(&issues);
+        self.check_link(&deps, &blocker, &blocked)?;
+
+        self.add_value(&update, &blocker, &self.issue.blocks_attr_key, &blocked)?;
+        self.add_value(&update, &blocked, &self.issue.blocked_by_attr_key, &blocker)?;
+        self.find = Some(find);
+        self.update = Some(update);
+        Ok(())
+    }
+}
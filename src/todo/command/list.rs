@@ -1,5 +1,6 @@
 use std::mem;
 use std::result::Result;
+use failure::Error;
 
 use todo::command::store::Find;
 use todo::command::Command;
@@ -15,6 +16,13 @@ where
     pub issue: Issue<String>,
 }
 
+impl<T> List<T>
+where
+    T: Find,
+{
+    pub const READY_VIEW: &'static str = "ready";
+}
+
 impl<T> Command for List<T>
 where
     T: Find,
@@ -29,6 +37,9 @@ where
                 self.issue.attrs.set_attr_value(param.to_lowercase().as_str(), value);
             }
         } else if let Some(find) = self.find.as_mut() {
+            if value == Self::READY_VIEW {
+                return find.set_param(Self::READY_VIEW, true.to_string());
+            }
             let default_key = find.default_param_key().to_string();
             find.set_param(&default_key, value)?;
         }
@@ -42,12 +53,13 @@ where
             .expect("Find command not exist")
     }
 
-    fn exec(&mut self) {
+    fn exec(&mut self) -> Result<(), Error> {
         let mut find =  mem::replace(&mut self.find, None)
             .expect("Find command not exist");
 
-        find.init_from(&self.issue);
-        find.exec();
+        find.init_from(&self.issue)?;
+        find.exec()?;
         self.find = Some(find);
+        Ok(())
     }
 }
@@ -0,0 +1,79 @@
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use chrono::NaiveDate;
+
+use date::Calendar;
+use todo::command::store::Find;
+use todo::command::Command;
+use todo::error::TodoError;
+use todo::issue::Issue;
+use todo::timelog::{self, TimeEntry, TimeLog};
+
+#[derive(Clone, Debug, Default)]
+pub struct Log<T>
+where
+    T: Find,
+{
+    pub find: Option<T>,
+    pub issue: Issue<String>,
+    pub time_log: TimeLog,
+    pub calendar: Calendar,
+    pub minutes: Option<i64>,
+    pub date: Option<NaiveDate>,
+}
+
+impl<T> Log<T>
+where
+    T: Find,
+{
+    pub const DATE_PARAM: &'static str = "date";
+}
+
+impl<T> Command for Log<T>
+where
+    T: Find,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if param == Self::DATE_PARAM {
+            self.date = Some(self.calendar.parse_date(&value).ok_or(TodoError::InvalidDate { value })?);
+        } else if !param.is_empty() {
+            return Err(TodoError::UnknownCommandParam { param: param.to_string() });
+        } else if self.issue.get_id().is_none() {
+            let id_attr_key = self.issue.id_attr_key.clone();
+            self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        } else {
+            self.minutes = Some(timelog::parse_duration(&value).ok_or(TodoError::InvalidDuration { value })?);
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        let id = self.issue.required_id()?.clone();
+        let minutes = self.minutes.ok_or(TodoError::ParamNotSpecified { param: "time".to_string() })?;
+        find.init_from(&self.issue)?;
+        find.find_issues()?
+            .first()
+            .ok_or(TodoError::IssueNotFound { id: id.clone() })?;
+
+        let now = self.calendar.now();
+        let start = self.date.map(|date| date.and_time(now.time())).unwrap_or(now);
+        self.time_log.add(TimeEntry { id: id.clone(), start, minutes: Some(minutes) })?;
+        println!(
+            "{} {} {}",
+            self.time_log.action_name("logged", "would log"),
+            id,
+            timelog::format_duration(minutes)
+        );
+        self.find = Some(find);
+        Ok(())
+    }
+}
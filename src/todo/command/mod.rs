@@ -1,9 +1,43 @@
+pub mod agenda;
+pub mod check;
+pub mod close;
+pub mod comment;
+pub mod export;
+pub mod history;
+pub mod import;
+pub mod link;
 pub mod list;
+pub mod log;
 pub mod new;
+pub mod report;
+pub mod show;
+pub mod start;
+pub mod stop;
 pub mod store;
+pub mod tag;
+pub mod tags;
+pub mod tree;
 
+pub use self::agenda::*;
+pub use self::check::*;
+pub use self::close::*;
+pub use self::comment::*;
+pub use self::export::*;
+pub use self::history::*;
+pub use self::import::*;
+pub use self::link::*;
 pub use self::list::*;
+pub use self::log::*;
 pub use self::new::*;
+pub use self::report::*;
+pub use self::show::*;
+pub use self::start::*;
+pub use self::stop::*;
+pub use self::tag::*;
+pub use self::tags::*;
+pub use self::tree::*;
+
+use failure::Error;
 
 use todo::error::TodoError;
 use todo::issue::{Content, Issue};
@@ -11,9 +45,9 @@ use todo::issue::{Content, Issue};
 pub trait Command {
     fn set_param(&mut self, key: &str, value: String) -> Result<(), TodoError>;
     fn default_param_key(&self) -> &str;
-    fn exec(&mut self);
+    fn exec(&mut self) -> Result<(), Error>;
 }
 
 pub trait IssueCommand: Command {
-    fn init_from<T: Content>(&mut self, issue: &Issue<T>);
+    fn init_from<T: Content>(&mut self, issue: &Issue<T>) -> Result<(), Error>;
 }
\ No newline at end of file
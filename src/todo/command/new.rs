@@ -1,6 +1,10 @@
+use std::fs;
+use std::io::{self, Read};
 use std::mem;
 use std::result::Result;
+use failure::{Error, ResultExt};
 
+use date::Calendar;
 use todo::command::store::Create;
 use todo::command::Command;
 use todo::error::TodoError;
@@ -13,6 +17,97 @@ where
 {
     pub create: Option<T>,
     pub issue: Issue<String>,
+    pub calendar: Calendar,
+    pub created_attr: Option<String>,
+    pub updated_attr: Option<String>,
+    pub author_attr: Option<String>,
+    pub author: Option<String>,
+    pub body: Option<String>,
+    pub read_stdin: bool,
+    pub stdin_piped: bool,
+}
+
+impl<T> New<T>
+where
+    T: Create,
+{
+    pub const BODY_PARAM: &'static str = "body";
+    pub const STDIN_BODY: &'static str = "-";
+    pub const FILE_BODY_PREFIX: char = '@';
+
+    /// Pipes and terminals may stay open without data, so only a redirected file is read implicitly
+    #[cfg(unix)]
+    pub fn is_stdin_redirected_from_file() -> bool {
+        fs::metadata("/dev/stdin")
+            .map(|metadata| metadata.is_file())
+            .unwrap_or(false)
+    }
+
+    /// Other platforms have no stdin path to check, so the body is read only with `body:-`
+    #[cfg(not(unix))]
+    pub fn is_stdin_redirected_from_file() -> bool {
+        false
+    }
+
+    #[cfg(unix)]
+    pub fn is_stdin_piped() -> bool {
+        use std::os::unix::fs::FileTypeExt;
+
+        fs::metadata("/dev/stdin")
+            .map(|metadata| metadata.file_type().is_fifo())
+            .unwrap_or(false)
+    }
+
+    #[cfg(not(unix))]
+    pub fn is_stdin_piped() -> bool {
+        false
+    }
+
+    pub fn read_body(&self) -> Result<Option<String>, Error> {
+        let mut body = String::new();
+        match self.body {
+            Some(ref source) if source == Self::STDIN_BODY => {
+                io::stdin().read_to_string(&mut body)?;
+            }
+            Some(ref source) if source.starts_with(Self::FILE_BODY_PREFIX) => {
+                let path = &source[1..];
+                body = fs::read_to_string(path).context(format!("Can't read body file: {}", path))?;
+            }
+            Some(ref text) => body = text.clone(),
+            None if self.read_stdin => {
+                io::stdin().read_to_string(&mut body)?;
+            }
+            None if self.stdin_piped => {
+                eprintln!("hint: piped input is not read, use `{}:{}` to take the body from stdin", Self::BODY_PARAM, Self::STDIN_BODY);
+            }
+            None => (),
+        }
+        Ok(if body.trim().is_empty() { None } else { Some(body) })
+    }
+
+    pub fn normalize_attr_value(&self, alias: &str, value: String) -> Result<String, TodoError> {
+        self.issue.normalize_attr_value(alias, value, &self.calendar)
+    }
+
+    pub fn stamp_attrs(&mut self) {
+        let timestamp = self.calendar.timestamp();
+        let mut stamps = Vec::new();
+        if let Some(ref key) = self.created_attr {
+            stamps.push((key.clone(), timestamp.clone()));
+        }
+        if let Some(ref key) = self.updated_attr {
+            stamps.push((key.clone(), timestamp));
+        }
+        if let (Some(key), Some(author)) = (self.author_attr.clone(), self.author.clone()) {
+            stamps.push((key, author));
+        }
+
+        for (key, value) in stamps {
+            if self.issue.attrs.attr_value_as_str(key.as_str()).is_empty() {
+                self.issue.attrs.set_attr_value(key.as_str(), value);
+            }
+        }
+    }
 }
 
 impl<T> Command for New<T>
@@ -20,13 +115,17 @@ where
     T: Create,
 {
     fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
-        if !param.is_empty() {
+        if param == Self::BODY_PARAM {
+            self.body = Some(value);
+        } else if !param.is_empty() {
             let mut is_create_param = false;
             if let Some(create) = self.create.as_mut() {
                 is_create_param = create.set_param(param, value.clone()).is_ok();
             }
             if !is_create_param {
-                self.issue.attrs.set_attr_value(param.to_lowercase().as_str(), value);
+                let param = param.to_lowercase();
+                let value = self.normalize_attr_value(param.as_str(), value)?;
+                self.issue.attrs.set_attr_value(param.as_str(), value);
             }
         } else {
             self.issue.attrs.set_default_attr(value);
@@ -38,12 +137,17 @@ where
         self.issue.attrs.default_key.as_str()
     }
 
-    fn exec(&mut self) {
+    fn exec(&mut self) -> Result<(), Error> {
         let mut create = mem::replace(&mut self.create, None)
             .expect("Create command not exist");
 
-        create.init_from(&self.issue);
-        create.exec();
+        self.stamp_attrs();
+        if let Some(body) = self.read_body()? {
+            self.issue.content = Some(body);
+        }
+        create.init_from(&self.issue)?;
+        create.exec()?;
         self.create = Some(create);
+        Ok(())
     }
 }
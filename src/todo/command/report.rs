@@ -0,0 +1,121 @@
+use std::collections::{BTreeMap, HashMap};
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use chrono::NaiveDate;
+
+use date::Calendar;
+use todo::command::store::Find;
+use todo::command::Command;
+use todo::error::TodoError;
+use todo::issue::Issue;
+use todo::timelog::{self, TimeLog};
+
+#[derive(Clone, Debug, Default)]
+pub struct Report<T>
+where
+    T: Find,
+{
+    pub find: Option<T>,
+    pub issue: Issue<String>,
+    pub time_log: TimeLog,
+    pub calendar: Calendar,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    pub group_by: Option<String>,
+}
+
+impl<T> Report<T>
+where
+    T: Find,
+{
+    pub const FROM_PARAM: &'static str = "from";
+    pub const TO_PARAM: &'static str = "to";
+    pub const GROUP_BY_PARAM: &'static str = "by";
+
+    fn group_key(&self, issue: &Issue<String>) -> String {
+        match self.group_by {
+            Some(ref key) if *key != issue.id_attr_key => {
+                let value = issue.attrs.attr_value_as_str(key);
+                if value.is_empty() { "-".to_string() } else { value.to_string() }
+            }
+            _ => {
+                let id = issue.get_id().map(String::as_str).unwrap_or("-");
+                if issue.get_title().is_empty() {
+                    id.to_string()
+                } else {
+                    format!("{} {}", id, issue.get_title())
+                }
+            }
+        }
+    }
+}
+
+impl<T> Command for Report<T>
+where
+    T: Find,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        let param = param.to_lowercase();
+        if param == Self::FROM_PARAM || param == Self::TO_PARAM {
+            let date = self.calendar.parse_date(&value).ok_or(TodoError::InvalidDate { value })?;
+            if param == Self::FROM_PARAM {
+                self.from = Some(date);
+            } else {
+                self.to = Some(date);
+            }
+        } else if param == Self::GROUP_BY_PARAM || param.is_empty() {
+            let key = self.issue.attrs.key_by_alias(&value).map(|key| (*key).clone()).unwrap_or(value);
+            self.group_by = Some(key);
+        } else {
+            let mut is_find_param = false;
+            if let Some(find) = self.find.as_mut() {
+                is_find_param = find.set_param(&param, value.clone()).is_ok();
+            }
+            if !is_find_param {
+                self.issue.attrs.set_attr_value(param.as_str(), value);
+            }
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        Self::GROUP_BY_PARAM
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        find.init_from(&self.issue)?;
+        let issues = find.find_issues()?;
+        let issues: HashMap<&str, &Issue<String>> = issues.iter()
+            .filter_map(|issue| issue.get_id().map(|id| (id.as_str(), issue)))
+            .collect();
+
+        let now = self.calendar.now();
+        let mut totals = BTreeMap::new();
+        let mut total = 0;
+        for entry in self.time_log.read_entries()? {
+            let date = entry.start.date();
+            if self.from.map(|from| date < from).unwrap_or(false)
+                || self.to.map(|to| date > to).unwrap_or(false)
+            {
+                continue;
+            }
+            if let Some(issue) = issues.get(entry.id.as_str()) {
+                let minutes = entry.minutes.unwrap_or_else(|| (now - entry.start).num_minutes().max(0));
+                *totals.entry(self.group_key(issue)).or_insert(0) += minutes;
+                total += minutes;
+            }
+        }
+
+        for (key, minutes) in &totals {
+            println!("{}: {}", key, timelog::format_duration(*minutes));
+        }
+        println!("total: {}", timelog::format_duration(total));
+        self.find = Some(find);
+        Ok(())
+    }
+}
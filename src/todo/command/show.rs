@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use todo::command::store::Find;
+use todo::command::Command;
+use todo::comments;
+use todo::dependency::{ChainLink, Dependencies};
+use todo::error::TodoError;
+use todo::hierarchy::Hierarchy;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Show<T>
+where
+    T: Find,
+{
+    pub find: Option<T>,
+    pub issue: Issue<String>,
+    pub comments_section: String,
+}
+
+impl<T> Show<T>
+where
+    T: Find,
+{
+    fn chain_lines(deps: &Dependencies, titles: &HashMap<String, String>, chain: Vec<ChainLink>) -> Vec<String> {
+        chain.into_iter()
+            .map(|link| {
+                let mut line = format!("{}{}", "  ".repeat(link.depth + 1), link.id);
+                match titles.get(&link.id) {
+                    Some(title) if !title.is_empty() => line = line + " " + title,
+                    Some(_) => (),
+                    None => line += " [missing]",
+                }
+                if deps.contains(&link.id) && !deps.is_open(&link.id) {
+                    line += " [closed]";
+                }
+                if link.is_cycle {
+                    line += " [cycle]";
+                }
+                line
+            })
+            .collect()
+    }
+}
+
+impl<T> Command for Show<T>
+where
+    T: Find,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            let mut is_find_param = false;
+            if let Some(find) = self.find.as_mut() {
+                is_find_param = find.set_param(param, value.clone()).is_ok();
+            }
+            if !is_find_param {
+                self.issue.attrs.set_attr_value(param.to_lowercase().as_str(), value);
+            }
+        } else {
+            let id_attr_key = self.issue.id_attr_key.clone();
+            self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        find.init_from(&self.issue)?;
+        let issues = find.find_issues()?;
+        if let (true, Some(id)) = (issues.is_empty(), self.issue.get_id()) {
+            return Err(TodoError::IssueNotFound { id: id.clone() }.into());
+        }
+
+        let mut all = self.issue.clone();
+        all.attrs.clear_values();
+        find.init_from(&all)?;
+        let all_issues = find.find_issues()?;
+        let deps = Dependencies::from_issues(&all_issues);
+        let hierarchy = Hierarchy::from_issues(&all_issues);
+        let titles: HashMap<String, String> = all_issues.iter()
+            .filter_map(|issue| issue.get_id().map(|id| (id.clone(), issue.get_title().to_string())))
+            .collect();
+
+        for (i, issue) in issues.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            let content = issue.content.clone().unwrap_or_default();
+            let (body, comments) = comments::split_comments(&content, &self.comments_section);
+            let body = body.trim_end();
+            if !body.is_empty() {
+                println!("{}", body);
+            }
+
+            if let Some((done, total)) = issue.get_progress() {
+                println!("{}: {}/{}", issue.progress_attr_key, done, total);
+            }
+            if let Some(id) = issue.get_id() {
+                let blockers = Self::chain_lines(&deps, &titles, deps.blockers_chain(id));
+                if !blockers.is_empty() {
+                    println!("{}:", issue.blocked_by_attr_key);
+                    println!("{}", blockers.join("\n"));
+                }
+                let blocked = Self::chain_lines(&deps, &titles, deps.blocked_chain(id));
+                if !blocked.is_empty() {
+                    println!("{}:", issue.blocks_attr_key);
+                    println!("{}", blocked.join("\n"));
+                }
+                if let Some((closed, total)) = hierarchy.progress(id) {
+                    println!("children: {}/{}", closed, total);
+                    for child in hierarchy.children(id) {
+                        println!("  {}", hierarchy.issue_line(child));
+                    }
+                }
+            }
+            if !comments.is_empty() {
+                println!("comments:");
+                for comment in &comments {
+                    println!("  {} {}:", comment.timestamp, comment.author);
+                    for line in comment.text.lines() {
+                        println!("    {}", line);
+                    }
+                }
+            }
+        }
+        self.find = Some(find);
+        Ok(())
+    }
+}
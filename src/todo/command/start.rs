@@ -0,0 +1,63 @@
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use date::Calendar;
+use todo::command::store::Find;
+use todo::command::Command;
+use todo::error::TodoError;
+use todo::issue::Issue;
+use todo::timelog::{self, TimeLog};
+
+#[derive(Clone, Debug, Default)]
+pub struct Start<T>
+where
+    T: Find,
+{
+    pub find: Option<T>,
+    pub issue: Issue<String>,
+    pub time_log: TimeLog,
+    pub calendar: Calendar,
+}
+
+impl<T> Command for Start<T>
+where
+    T: Find,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            return Err(TodoError::UnknownCommandParam { param: param.to_string() });
+        }
+        let id_attr_key = self.issue.id_attr_key.clone();
+        self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        let id = self.issue.required_id()?.clone();
+        find.init_from(&self.issue)?;
+        find.find_issues()?
+            .first()
+            .ok_or(TodoError::IssueNotFound { id: id.clone() })?;
+
+        let stopped = self.time_log.start(&id, self.calendar.now())?;
+        if let Some(entry) = stopped {
+            println!(
+                "{} {} {}",
+                self.time_log.action_name("stopped", "would stop"),
+                entry.id,
+                timelog::format_duration(entry.minutes.unwrap_or(0))
+            );
+        }
+        println!("{} {}", self.time_log.action_name("started", "would start"), id);
+        self.find = Some(find);
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use std::result::Result;
+use failure::Error;
+
+use date::Calendar;
+use todo::command::Command;
+use todo::error::TodoError;
+use todo::timelog::{self, TimeLog};
+
+#[derive(Clone, Debug, Default)]
+pub struct Stop {
+    pub time_log: TimeLog,
+    pub calendar: Calendar,
+}
+
+impl Command for Stop {
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        Err(TodoError::UnknownCommandParam {
+            param: if param.is_empty() { value } else { param.to_string() },
+        })
+    }
+
+    fn default_param_key(&self) -> &str {
+        ""
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let stopped = self.time_log.stop(self.calendar.now())?;
+        if let Some(entry) = stopped {
+            println!(
+                "{} {} {}",
+                self.time_log.action_name("stopped", "would stop"),
+                entry.id,
+                timelog::format_duration(entry.minutes.unwrap_or(0))
+            );
+        }
+        Ok(())
+    }
+}
@@ -1 +1,127 @@
+use failure::Error;
 
+use todo::command::store::fs::{AttrParser, AttrSpan, AttrsStyle};
+
+pub trait AttrsContent {
+    fn line_end(&self, pos: usize) -> usize;
+    fn set_attr(&mut self, parser: &AttrParser, style: AttrsStyle, key: &str, value: &str) -> Result<(), Error>;
+    fn remove_attr(&mut self, parser: &AttrParser, key: &str) -> Result<(), Error>;
+    fn strip_attrs(&mut self, parser: &AttrParser) -> Result<Vec<(String, String)>, Error>;
+}
+
+impl AttrsContent for String {
+    fn line_end(&self, pos: usize) -> usize {
+        self[pos..].find('\n')
+            .map(|index| pos + index + 1)
+            .unwrap_or_else(|| self.len())
+    }
+
+    fn set_attr(&mut self, parser: &AttrParser, style: AttrsStyle, key: &str, value: &str) -> Result<(), Error> {
+        let spans = parser.read_attr_spans(self.as_bytes())?;
+        let key_spans: Vec<&AttrSpan> = spans.iter()
+            .filter(|span| span.key == key)
+            .collect();
+
+        if let Some(first) = key_spans.first() {
+            for span in key_spans.iter().skip(1).rev() {
+                let end = self.line_end(span.end);
+                self.replace_range(span.start..end, "");
+            }
+            let attr = AttrParser::encode_front_matter_attr(first.style, key, value);
+            self.replace_range(first.start..first.end, &attr);
+        } else if let Some((front_matter_style, pos)) = parser.front_matter_end(self) {
+            let attr = AttrParser::encode_front_matter_attr(front_matter_style, key, value);
+            self.insert_str(pos, &format!("{}\n", attr));
+        } else if let Some(last) = spans.last() {
+            let attr = AttrParser::encode_attr(key, value);
+            if self.line_end(last.end) == self.len() && !self.ends_with('\n') {
+                self.push('\n');
+            }
+            let pos = self.line_end(last.end);
+            self.insert_str(pos, &format!("{}\n", attr));
+        } else {
+            self.insert_str(0, &AttrParser::encode_attrs(style, &[(key, value)]));
+        }
+        Ok(())
+    }
+
+    fn remove_attr(&mut self, parser: &AttrParser, key: &str) -> Result<(), Error> {
+        let spans = parser.read_attr_spans(self.as_bytes())?;
+        for span in spans.iter().filter(|span| span.key == key).collect::<Vec<_>>().iter().rev() {
+            let end = self.line_end(span.end);
+            self.replace_range(span.start..end, "");
+        }
+        Ok(())
+    }
+
+    fn strip_attrs(&mut self, parser: &AttrParser) -> Result<Vec<(String, String)>, Error> {
+        let attrs = parser.read_attrs(self.as_bytes())?;
+        if let Some((_, pos)) = parser.front_matter_end(self) {
+            let end = self.line_end(pos);
+            self.replace_range(..end, "");
+        }
+        for (key, _) in parser.read_attrs(self.as_bytes())? {
+            self.remove_attr(parser, &key)?;
+        }
+        Ok(attrs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_attr() {
+        let parser = AttrParser::new();
+
+        let mut content = "#[key: value] // comment\ntext\n".to_string();
+        content.set_attr(&parser, AttrsStyle::Attr, "key", "new value").unwrap();
+        assert_eq!("#[key: new value] // comment\ntext\n", content);
+
+        content.set_attr(&parser, AttrsStyle::Attr, "key 2", "value 2").unwrap();
+        assert_eq!("#[key: new value] // comment\n#[key 2: value 2]\ntext\n", content);
+
+        let mut content = "#[tags: a]\n#[tags: b]\ntext\n#[tags: c]".to_string();
+        content.set_attr(&parser, AttrsStyle::Attr, "tags", "a, b, c").unwrap();
+        assert_eq!("#[tags: a, b, c]\ntext\n", content);
+
+        let mut content = "---\nkey: value\n---\ntext\n".to_string();
+        content.set_attr(&parser, AttrsStyle::Attr, "key", "new: value").unwrap();
+        content.set_attr(&parser, AttrsStyle::Attr, "key 2", "value 2").unwrap();
+        assert_eq!("---\nkey: \"new: value\"\nkey 2: value 2\n---\ntext\n", content);
+
+        let mut content = "text".to_string();
+        content.set_attr(&parser, AttrsStyle::Toml, "key", "value").unwrap();
+        assert_eq!("+++\nkey = \"value\"\n+++\ntext", content);
+
+        let mut content = "#[key: value]".to_string();
+        content.set_attr(&parser, AttrsStyle::Attr, "key 2", "value 2").unwrap();
+        assert_eq!("#[key: value]\n#[key 2: value 2]\n", content);
+    }
+
+    #[test]
+    fn remove_attr() {
+        let parser = AttrParser::new();
+
+        let mut content = "#[key: value] // comment\n#[key 2: value 2]\ntext\n#[key:\nvalue]".to_string();
+        content.remove_attr(&parser, "key").unwrap();
+        assert_eq!("#[key 2: value 2]\ntext\n", content);
+
+        let mut content = "+++\nkey = \"value\"\nkey2 = \"value\"\n+++\ntext\n".to_string();
+        content.remove_attr(&parser, "key").unwrap();
+        assert_eq!("+++\nkey2 = \"value\"\n+++\ntext\n", content);
+    }
+
+    #[test]
+    fn strip_attrs() {
+        let parser = AttrParser::new();
+
+        let mut content = "---\nkey: value\n---\n#[key 2: value 2]\ntext\n".to_string();
+        assert_eq!(
+            vec![("key".to_string(), "value".to_string()), ("key 2".to_string(), "value 2".to_string())],
+            content.strip_attrs(&parser).unwrap()
+        );
+        assert_eq!("text\n", content);
+    }
+}
@@ -1,9 +1,12 @@
+use std::collections::HashMap;
 use std::fs;
 use std::io::Write;
 use std::path::Path;
+use failure::{Error, ResultExt};
 
+use editor;
 use todo::attrs::Attrs;
-use todo::command::store::fs::{AttrParser, Format, SequenceGenerator};
+use todo::command::store::fs::{Action, AttrParser, AttrsContent, AttrsStyle, Format, Journal, SequenceGenerator};
 use todo::command::store::Create as CanCreate;
 use todo::command::{Command, IssueCommand};
 use todo::error::TodoError;
@@ -12,9 +15,17 @@ use todo::issue::{Content, Issue};
 #[derive(Clone, Debug, Default)]
 pub struct Create {
     content: String,
+    edited_issue: Option<Issue<String>>,
     pub attrs: Attrs,
     pub path: Option<String>,
     pub id_generator: Option<SequenceGenerator>,
+    pub journal: Journal,
+    pub generated_id: Option<String>,
+    pub dry_run: bool,
+    pub editor: String,
+    pub skeleton: String,
+    pub templates_dir: String,
+    pub templates: HashMap<String, String>,
 }
 
 #[derive(PartialEq)]
@@ -22,6 +33,9 @@ pub enum CreateAttr {
     IssuesDir,
     Format,
     Ext,
+    AttrsStyle,
+    Edit,
+    Template,
 }
 
 impl CreateAttr {
@@ -30,6 +44,9 @@ impl CreateAttr {
             key if CreateAttr::IssuesDir.key() == key => CreateAttr::IssuesDir,
             key if CreateAttr::Format.key() == key => CreateAttr::Format,
             key if CreateAttr::Ext.key() == key => CreateAttr::Ext,
+            key if CreateAttr::AttrsStyle.key() == key => CreateAttr::AttrsStyle,
+            key if CreateAttr::Edit.key() == key => CreateAttr::Edit,
+            key if CreateAttr::Template.key() == key => CreateAttr::Template,
             _ => return None,
         })
     }
@@ -39,10 +56,157 @@ impl CreateAttr {
             CreateAttr::IssuesDir => "issues_dir",
             CreateAttr::Format => "format",
             CreateAttr::Ext => "ext",
+            CreateAttr::AttrsStyle => "attrs_style",
+            CreateAttr::Edit => "edit",
+            CreateAttr::Template => "template",
         }
     }
 }
 
+impl Create {
+    pub fn attrs_style(&self) -> Result<AttrsStyle, TodoError> {
+        let style = self.attrs.attr_value_as_str(CreateAttr::AttrsStyle.key());
+        if style.is_empty() {
+            Ok(AttrsStyle::Attr)
+        } else {
+            AttrsStyle::by_key(style)
+                .ok_or_else(|| TodoError::UnsupportedAttrsStyle { style: style.to_string() })
+        }
+    }
+
+    pub fn is_edit(&self) -> bool {
+        self.attrs
+            .attr_value(CreateAttr::Edit.key())
+            .map(|value| value == "true")
+            .unwrap_or(false)
+    }
+
+    pub fn template_text(&self, name: &str) -> Result<String, Error> {
+        if let Some(text) = self.templates.get(name) {
+            return Ok(text.clone());
+        }
+
+        let ext = self.attrs.attr_value_as_str(CreateAttr::Ext.key());
+        let dir = Path::new(&self.templates_dir);
+        for path in &[dir.join(format!("{}.{}", name, ext)), dir.join(name)] {
+            if path.is_file() {
+                return Ok(fs::read_to_string(path)?);
+            }
+        }
+        Err(TodoError::TemplateNotFound { name: name.to_string() }.into())
+    }
+
+    pub fn apply_template(&self, issue: &mut Issue<String>, id: &str) -> Result<(), Error> {
+        let name = self.attrs.attr_value_as_str(CreateAttr::Template.key());
+        if name.is_empty() {
+            return Ok(());
+        }
+
+        let mut text = self.template_text(name)?;
+        for key in &issue.attrs.keys {
+            if **key == issue.id_attr_key {
+                text.key_replace(key, id);
+            } else {
+                text.key_replace(key, issue.attrs.attr_value_as_str(key));
+            }
+        }
+
+        let parser = AttrParser::new();
+        for (key, value) in parser.read_attrs(text.as_bytes())? {
+            text.remove_attr(&parser, &key)?;
+            if issue.attrs.attr_value_as_str(&key).is_empty() {
+                issue.attrs.set_attr_value(key.as_str(), parser.parse_value(value.as_str()).0);
+            }
+        }
+        if issue.content.is_none() && !text.trim().is_empty() {
+            issue.content = Some(text);
+        }
+        Ok(())
+    }
+
+    fn edit_content(&mut self, mut issue: Issue<String>) -> Result<(), Error> {
+        let mut text = editor::edit_text(
+            &self.editor,
+            &format!("{}{}", self.content, self.skeleton),
+            self.attrs.attr_value_as_str(CreateAttr::Ext.key()),
+        )?;
+        if text.trim().is_empty() {
+            return Err(TodoError::EditAborted.into());
+        }
+
+        // Attrs removed in the editor must not come back from the command line
+        let parser = AttrParser::new();
+        for (key, _) in parser.read_attrs(self.content.as_bytes())? {
+            issue.attrs.set_attr_value(key.as_str(), "");
+        }
+        for (key, value) in text.strip_attrs(&parser)? {
+            issue.attrs.set_attr_value(key.as_str(), value);
+        }
+        issue.content = if text.trim().is_empty() { None } else { Some(text) };
+        self.render(&issue, true, false)
+    }
+
+    fn render(&mut self, issue: &Issue<String>, take_id: bool, use_template: bool) -> Result<(), Error> {
+        let mut issue = issue.clone();
+        let mut format = self.attrs
+            .attr_value_as_str(CreateAttr::Format.key())
+            .to_string();
+
+        let id_found = format
+            .find(&issue.id_attr_key)
+            .and_then(|pos| format.key_replaceable_pos(pos, issue.id_attr_key.len()))
+            .is_some();
+
+        self.generated_id = None;
+        let id = match self.id_generator {
+            Some(ref generator) if id_found && issue.get_id().is_none() => {
+                let id = if take_id { generator.next()? } else { generator.peek()? };
+                self.generated_id = Some(id.clone());
+                id
+            }
+            _ => issue.get_id().cloned().unwrap_or_default(),
+        };
+        if id_found {
+            format.key_replace(&issue.id_attr_key, id.as_str());
+        }
+        if use_template {
+            self.apply_template(&mut issue, &id)?;
+        }
+
+        format.key_replace(
+            CreateAttr::Ext.key(),
+            self.attrs.attr_value_as_str(CreateAttr::Ext.key()),
+        );
+        let mut content_attrs = Vec::new();
+        for key in &issue.attrs.keys {
+            let key = key.as_str();
+            if (!id_found || key != issue.id_attr_key) && key != CreateAttr::Ext.key() {
+                let value = if issue.attrs.is_multi_value_key(key) {
+                    Attrs::join_values(&issue.get_values(key))
+                } else {
+                    issue.attrs.attr_value_as_str(key).to_string()
+                };
+                if !format.key_replace(key, &value) && !value.is_empty() {
+                    content_attrs.push((key, value));
+                }
+            }
+        }
+        self.content = AttrParser::encode_attrs(self.attrs_style()?, &content_attrs);
+        if let Some(ref content) = issue.content {
+            let body = content.content();
+            self.content += &body;
+            if !body.ends_with('\n') {
+                self.content.push('\n');
+            }
+        }
+
+        if let Some(dir) = self.attrs.attr_value(CreateAttr::IssuesDir.key()) {
+            self.path = Some(format!("{}/{}", dir, format));
+        }
+        Ok(())
+    }
+}
+
 impl Command for Create {
     fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
         if let Some(key) = self.attrs.key_by_alias(param.to_lowercase().as_str()) {
@@ -64,64 +228,75 @@ impl Command for Create {
         self.attrs.default_key.as_str()
     }
 
-    fn exec(&mut self) {
+    fn exec(&mut self) -> Result<(), Error> {
+        if let Some(issue) = self.edited_issue.take() {
+            if self.path.is_some() {
+                self.edit_content(issue)?;
+            }
+        }
         if let Some(ref str_path) = self.path {
+            if self.dry_run {
+                if let Some(ref id) = self.generated_id {
+                    println!("would generate id {}", id);
+                }
+                println!("would create {}", str_path);
+                print!("{}", self.content);
+                return Ok(());
+            }
             let path = Path::new(str_path);
 
-            fs::File::open(path).expect_err(&format!("File {} already exists", str_path));
+            if path.exists() {
+                return Err(TodoError::FileAlreadyExists { path: str_path.clone() }.into());
+            }
             if let Some(dir) = path.parent() {
-                fs::create_dir_all(dir).expect(&format!("Can't create dir: {:?}", dir));
+                fs::create_dir_all(dir).context(format!("Can't create dir: {:?}", dir))?;
             }
             let mut file = fs::File::create(path)
-                .expect(&format!("Creation error with path: {}", str_path));
+                .context(format!("Creation error with path: {}", str_path))?;
             if !self.content.is_empty() {
                 file.write_all(self.content.as_bytes())
-                    .expect(&format!("Error content write to file: {}", str_path));
+                    .context(format!("Error content write to file: {}", str_path))?;
             }
+            self.journal.record(Action::Create { path: str_path.clone(), content: self.content.clone() })?;
 
             println!("{}", str_path);
         }
+        Ok(())
     }
 }
 
 impl IssueCommand for Create {
-    fn init_from<T: Content>(&mut self, issue: &Issue<T>) {
-        let mut format = self.attrs
-            .attr_value_as_str(CreateAttr::Format.key())
-            .to_string();
-
-        let id_found = format
-            .find(&issue.id_attr_key)
-            .and_then(|pos| format.key_replaceable_pos(pos, issue.id_attr_key.len()))
-            .is_some();
-
-        if id_found {
-            let id = match self.id_generator {
-                Some(ref generator) if issue.get_id().is_none() =>
-                    generator.next().expect("Generate next id fail"),
-                _ => issue.get_id().cloned().unwrap_or_default(),
-            };
-            format.key_replace(&issue.id_attr_key, id.as_str());
+    fn init_from<T: Content>(&mut self, issue: &Issue<T>) -> Result<(), Error> {
+        let issue = issue.to_string_issue();
+        // The id is taken after editing, so an aborted editor doesn't advance the sequence
+        let is_edit = self.is_edit() && !self.dry_run;
+        self.render(&issue, !is_edit, true)?;
+        if is_edit {
+            self.edited_issue = Some(issue);
         }
+        Ok(())
+    }
+}
 
-        format.key_replace(
-            CreateAttr::Ext.key(),
-            self.attrs.attr_value_as_str(CreateAttr::Ext.key()),
-        );
-        for key in &issue.attrs.keys {
-            let key = key.as_str();
-            if !id_found || key != issue.id_attr_key {
-                let value = issue.attrs.attr_value_as_str(key);
-                if !format.key_replace(key, value) && !value.is_empty() {
-                    self.content += &format!("{}\n", AttrParser::encode_attr(key, value));
-                }
-            }
+impl CanCreate for Create {
+    fn generates_id(&self) -> bool {
+        self.id_generator.is_some()
+    }
+
+    fn reserve_id(&mut self, id: &str) -> Result<(), Error> {
+        match self.id_generator {
+            Some(ref generator) => generator.reserve(id),
+            None => Ok(()),
         }
+    }
 
-        if let Some(dir) = self.attrs.attr_value(CreateAttr::IssuesDir.key()) {
-            self.path = Some(format!("{}/{}", dir, format));
+    fn target_path(&self, issue: &Issue<String>) -> Result<Option<String>, Error> {
+        let mut create = self.clone();
+        create.dry_run = true;
+        if let Some(generator) = create.id_generator.as_mut() {
+            generator.dry_run = true;
         }
+        create.init_from(issue)?;
+        Ok(create.path)
     }
 }
-
-impl CanCreate for Create {}
@@ -0,0 +1,68 @@
+use std::mem;
+use failure::Error;
+
+use date::Calendar;
+use editor;
+use todo::command::store::fs::{AttrsContent, Update};
+use todo::command::store::Update as CanUpdate;
+use todo::command::{Command, IssueCommand};
+use todo::error::TodoError;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Edit {
+    pub update: Option<Update>,
+    pub issue: Issue<String>,
+    pub editor: String,
+    pub ext: String,
+    pub calendar: Calendar,
+}
+
+impl Command for Edit {
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            let mut is_update_param = false;
+            if let Some(update) = self.update.as_mut() {
+                is_update_param = update.set_param(param, value.clone()).is_ok();
+            }
+            if !is_update_param {
+                return Err(TodoError::UnknownCommandParam { param: param.to_string() });
+            }
+        } else {
+            let id_attr_key = self.issue.id_attr_key.clone();
+            self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut update = mem::replace(&mut self.update, None)
+            .expect("Update command not exist");
+
+        self.issue.required_id()?;
+        update.init_from(&self.issue)?;
+
+        let parser = update.find.attr_parser();
+        let style = update.attrs_style()?;
+        let (editor, ext, calendar) = (&self.editor, &self.ext, &self.calendar);
+        update.update(|issue| {
+            let mut text = editor::edit_text(editor, &issue.content.clone().unwrap_or_default(), ext)?;
+            // Edited attrs are checked and normalized the same way as `new` params
+            for (key, value) in parser.read_attrs(text.as_bytes())? {
+                let value = parser.parse_value(value.as_str()).0;
+                let normalized = issue.normalize_attr_value(&key, value.clone(), calendar)?;
+                if normalized != value {
+                    text.set_attr(&parser, style, &key, &normalized)?;
+                }
+            }
+            issue.content = Some(text);
+            Ok(())
+        })?;
+        self.update = Some(update);
+        Ok(())
+    }
+}
@@ -1,22 +1,51 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::fs::File;
+use std::io::Read;
 use regex::Regex;
-use failure::Error;
+use failure::{Error, ResultExt};
 use walkdir::{DirEntry, WalkDir};
 
+use date::{Calendar, DateFilter};
+use expr::{self, Expr, Ops};
 use todo::attrs::Attrs;
 use todo::command::store::Find as CanFind;
-use todo::command::store::fs::AttrParser;
+use todo::command::store::fs::{AttrParser, AttrsContent, CreateAttr};
+use todo::checklist::{self, ProgressFilter};
 use todo::command::{Command, IssueCommand};
+use todo::dependency::Dependencies;
 use todo::error::TodoError;
-use todo::issue::{Content, Issue};
+use todo::exchange::IssueRecord;
+use todo::issue::{body_tags, Content, Issue};
+
+#[derive(Clone, Debug)]
+pub struct IssueFile {
+    pub path: String,
+    pub path_attrs: Vec<(String, String)>,
+    pub issue: Issue<String>,
+}
+
+impl IssueFile {
+    pub fn load_content(&mut self) -> Result<(), Error> {
+        if self.issue.content.is_none() {
+            let mut text = String::new();
+            File::open(&self.path)?.read_to_string(&mut text)?;
+            self.issue.content = Some(text);
+        }
+        Ok(())
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Find {
-    issue_attrs: Option<Attrs>,
+    issue: Option<Issue<String>>,
     pub attrs: Attrs,
     pub filter: Option<Regex>,
+    pub ops: Ops,
+    pub calendar: Calendar,
+    pub trash: bool,
 }
 
 #[derive(EnumIterator, PartialEq)]
@@ -25,6 +54,11 @@ pub enum FindAttr {
     Capture,
     Filter,
     All,
+    HeaderOnly,
+    HeaderLimit,
+    Sort,
+    Columns,
+    Ready,
 }
 
 impl FindAttr {
@@ -34,6 +68,11 @@ impl FindAttr {
             key if FindAttr::Capture.key() == key => FindAttr::Capture,
             key if FindAttr::Filter.key() == key => FindAttr::Filter,
             key if FindAttr::All.key() == key => FindAttr::All,
+            key if FindAttr::HeaderOnly.key() == key => FindAttr::HeaderOnly,
+            key if FindAttr::HeaderLimit.key() == key => FindAttr::HeaderLimit,
+            key if FindAttr::Sort.key() == key => FindAttr::Sort,
+            key if FindAttr::Columns.key() == key => FindAttr::Columns,
+            key if FindAttr::Ready.key() == key => FindAttr::Ready,
             _ => return None,
         })
     }
@@ -44,11 +83,18 @@ impl FindAttr {
             FindAttr::Capture => "capture",
             FindAttr::Filter => "filter",
             FindAttr::All => "all",
+            FindAttr::HeaderOnly => "header_only",
+            FindAttr::HeaderLimit => "header_limit",
+            FindAttr::Sort => "sort",
+            FindAttr::Columns => "columns",
+            FindAttr::Ready => "ready",
         }
     }
 }
 
 impl Find {
+    pub const TRASH_DIR: &'static str = ".trash";
+
     fn is_hidden(entry: &DirEntry) -> bool {
         entry
             .file_name()
@@ -57,47 +103,205 @@ impl Find {
             .unwrap_or(false)
     }
 
-    pub fn all(&self) -> bool {
+    fn is_trash(entry: &DirEntry) -> bool {
+        entry.file_name() == Find::TRASH_DIR
+    }
+
+    fn is_root_chunk(&self, issues_dir: &OsStr, prev_chunk: &OsStr, chunk: &OsStr) -> bool {
+        if self.trash {
+            chunk == Find::TRASH_DIR && (issues_dir.is_empty() || prev_chunk == issues_dir)
+        } else {
+            issues_dir.is_empty() || chunk == issues_dir
+        }
+    }
+
+    fn is_set(&self, attr: FindAttr) -> bool {
         self.attrs
-            .attr_value(FindAttr::All.key())
+            .attr_value(attr.key())
             .map(|value| {
                 !["false", "f", "not", "no", "n", "0"].contains(&value.to_lowercase().as_str())
             })
             .unwrap_or(false)
     }
 
-    pub fn capture_regex(&self) -> Option<Regex> {
+    pub fn all(&self) -> bool {
+        self.is_set(FindAttr::All)
+    }
+
+    pub fn header_only(&self) -> bool {
+        self.is_set(FindAttr::HeaderOnly)
+    }
+
+    pub fn ready(&self) -> bool {
+        self.is_set(FindAttr::Ready)
+    }
+
+    pub fn header_limit(&self) -> Option<usize> {
         self.attrs
-            .attr_value(FindAttr::Capture.key())
-            .and_then(|value| {
-                if !value.is_empty() {
-                    Some(
-                        Regex::new(value.as_str())
-                            .expect(&format!("It isn't correct regex string: `{}`", value))
-                    )
+            .attr_value(FindAttr::HeaderLimit.key())
+            .and_then(|value| value.parse().ok())
+    }
+
+    pub fn is_header_limited(&self) -> bool {
+        self.header_only() || self.header_limit().is_some()
+    }
+
+    pub fn attr_parser(&self) -> AttrParser {
+        let mut parser = AttrParser::new();
+        parser.header_only = self.header_only();
+        parser.lines_limit = self.header_limit();
+        parser
+    }
+
+    pub fn capture_regex(&self) -> Result<Option<Regex>, TodoError> {
+        match self.attrs.attr_value(FindAttr::Capture.key()) {
+            Some(value) if !value.is_empty() => Regex::new(value.as_str())
+                .map(Some)
+                .map_err(|_| TodoError::InvalidRegex { value: value.clone() }),
+            _ => Ok(None),
+        }
+    }
+
+    fn values_of(parser: &AttrParser, attrs: &[(String, String)], key: &str) -> Vec<String> {
+        attrs.iter()
+            .filter(|&&(ref attr_key, _)| attr_key == key)
+            .flat_map(|&(_, ref attr_value)| {
+                Attrs::split_values(&parser.parse_value(attr_value.as_str()).0)
+            })
+            .collect()
+    }
+
+    pub fn value_exprs(&self) -> Result<HashMap<String, Expr>, Error> {
+        let mut exprs = HashMap::new();
+        if let Some(ref issue) = self.issue {
+            let issue_attrs = &issue.attrs;
+            for (key, value) in issue_attrs.iter() {
+                if issue_attrs.is_multi_value_key(key) && !value.is_empty() {
+                    exprs.insert(key.clone(), expr::parse(value, &self.ops)?);
+                }
+            }
+        }
+        Ok(exprs)
+    }
+
+    fn path_attrs(capture_regex: &Option<Regex>, prefix_path: &Path, path: &str) -> Vec<(String, String)> {
+        let mut attrs = Vec::new();
+        if let Some(ref regex) = *capture_regex {
+            let prefix_len = prefix_path.to_str()
+                .map(|path| {
+                    let len = path[2..].len();
+                    if len > 0 { len + 1 } else { len }
+                })
+                .unwrap_or(0);
+            if let Some(caps) = regex.captures(&path[prefix_len..]) {
+                for maybe_name in regex.capture_names() {
+                    let name = maybe_name.unwrap_or("");
+                    if !name.is_empty() {
+                        let text = caps.name(name)
+                            .map(|m| m.as_str())
+                            .unwrap_or("");
+                        attrs.push((name.to_string(), text.to_string()));
+                    }
+                }
+            }
+        }
+        attrs
+    }
+
+    pub fn date_filter(&self) -> Result<Option<DateFilter>, Error> {
+        if let Some(ref issue) = self.issue {
+            if let Some(value) = issue.attrs.attr_value(&issue.due_attr_key) {
+                return DateFilter::parse(value, &self.calendar)
+                    .map(Some)
+                    .ok_or_else(|| TodoError::InvalidDate { value: value.clone() }.into());
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn progress_filter(&self) -> Result<Option<ProgressFilter>, Error> {
+        if let Some(ref issue) = self.issue {
+            if let Some(value) = issue.attrs.attr_value(&issue.progress_attr_key) {
+                return ProgressFilter::parse(value)
+                    .map(Some)
+                    .ok_or_else(|| TodoError::InvalidProgress { value: value.clone() }.into());
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn sort_issue_files(&self, issue_files: &mut Vec<IssueFile>) {
+        let sort_keys = Attrs::split_values(self.attrs.attr_value_as_str(FindAttr::Sort.key()));
+        issue_files.sort_by(|a, b| {
+            let mut ordering = Ordering::Equal;
+            for sort_key in &sort_keys {
+                let (key, desc) = if sort_key.starts_with('-') {
+                    (&sort_key[1..], true)
                 } else {
-                    None
+                    (sort_key.as_str(), false)
+                };
+                let a = a.issue.attrs.attr_value_as_str(key);
+                let b = b.issue.attrs.attr_value_as_str(key);
+                ordering = match (a.is_empty(), b.is_empty()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    _ if desc => b.cmp(a),
+                    _ => a.cmp(b),
+                };
+                if ordering != Ordering::Equal {
+                    break;
                 }
-            })
+            }
+            ordering
+        });
     }
 
-    pub fn walk_through_issues(&self, root: &Path) -> Result<(), Error> {
+    pub fn issue_line(&self, issue_file: &IssueFile) -> String {
+        let columns = Attrs::split_values(self.attrs.attr_value_as_str(FindAttr::Columns.key()));
+        let mut line = String::new();
+        for key in &columns {
+            let issue = &issue_file.issue;
+            let value = if *key == issue.progress_attr_key {
+                issue.get_progress()
+                    .map(|(done, total)| format!("{}/{}", done, total))
+                    .unwrap_or_default()
+            } else {
+                issue.attrs.attr_value_as_str(key).to_string()
+            };
+            line += if value.is_empty() { "-" } else { value.as_str() };
+            line += " ";
+        }
+        line + &issue_file.path
+    }
+
+    pub fn walk_through_issues<F>(&self, root: &Path, mut visit: F) -> Result<(), Error>
+    where
+        F: FnMut(&str, Vec<(String, String)>) -> Result<(), Error>,
+    {
         let walker = WalkDir::new(root)
             .follow_links(true)
             .into_iter();
         let issues_dir = OsStr::new(self.attrs.attr_value_as_str(FindAttr::IssuesDir.key()));
-        let capture_regex = self.capture_regex();
+        let capture_regex = self.capture_regex()?;
+        let parser = self.attr_parser();
+        let value_exprs = self.value_exprs()?;
+        let date_filter = self.date_filter()?;
+        let progress_filter = self.progress_filter()?;
 
         for entry in walker.filter_entry(
-            |e| self.all() || !Find::is_hidden(e)
+            |e| self.all() || !Find::is_hidden(e) || (self.trash && Find::is_trash(e))
         ) {
             let entry = entry?;
             let mut prefix_path = PathBuf::new();
+            let mut prev_chunk = OsStr::new("");
             if entry.file_type().is_file() {
                 'path_chunks: for chunk in entry.path().iter() {
                     prefix_path = prefix_path.join(chunk);
 
-                    if issues_dir.is_empty() || chunk == issues_dir {
+                    if !self.is_root_chunk(issues_dir, prev_chunk, chunk) {
+                        prev_chunk = chunk;
+                    } else {
                         let maybe_path = entry.path()
                             .as_os_str()
                             .to_str()
@@ -107,44 +311,57 @@ impl Find {
                             if self.filter.is_none()
                                 || self.filter.as_ref().unwrap().is_match(path)
                             {
-                                if let Some(ref issue_attrs) = self.issue_attrs {
+                                let path_attrs = Find::path_attrs(&capture_regex, &prefix_path, path);
+
+                                if let Some(ref issue) = self.issue {
+                                    let issue_attrs = &issue.attrs;
                                     if issue_attrs.count() > 0 {
-                                        let mut attrs = Vec::new();
+                                        let mut attrs = path_attrs.clone();
+                                        let is_tags_filter = issue_attrs.attr_value(&issue.tags_attr_key).is_some();
+                                        let mut text = String::new();
 
-                                        if let Some(ref regex) = capture_regex {
-                                            let prefix_len = prefix_path.to_str()
-                                                .map(|path| {
-                                                    let len = path[2..].len();
-                                                    if len > 0 { len + 1 } else { len }
-                                                })
-                                                .unwrap_or(0);
-                                            if let Some(caps) = regex.captures(&path[prefix_len..]) {
-                                                for maybe_name in regex.capture_names() {
-                                                    let name = maybe_name.unwrap_or("");
-                                                    if !name.is_empty() {
-                                                        let text = caps.name(name)
-                                                            .map(|m| m.as_str())
-                                                            .unwrap_or("");
-                                                        attrs.push((name.to_string(), text.to_string()));
-                                                    }
+                                        if is_tags_filter || progress_filter.is_some() {
+                                            File::open(path)?.read_to_string(&mut text)?;
+                                            attrs.extend(parser.read_attrs(text.as_bytes())?);
+                                            if is_tags_filter {
+                                                for tag in body_tags(&text) {
+                                                    attrs.push((issue.tags_attr_key.clone(), tag));
                                                 }
                                             }
+                                        } else {
+                                            let file = File::open(path)?;
+                                            attrs.extend(parser.read_attrs(file)?);
                                         }
 
-                                        let file = File::open(path)?;
-                                        let parser = AttrParser::new();
-                                        attrs.extend(parser.read_attrs(file)?);
-
                                         for (key, value) in issue_attrs.iter() {
-                                            if attrs.iter().find(|&&(ref attr_key, ref attr_value)| {
-                                                attr_key == key && parser.parse_value(attr_value.as_str()).0 == *value
-                                            }).is_none() {
+                                            let is_match = if let Some(expr) = value_exprs.get(key) {
+                                                expr.calc(&Find::values_of(&parser, &attrs, key))
+                                            } else if *key == issue.due_attr_key && date_filter.is_some() {
+                                                let value = attrs.iter()
+                                                    .rev()
+                                                    .find(|&&(ref attr_key, _)| attr_key == key)
+                                                    .map(|&(_, ref attr_value)| parser.parse_value(attr_value.as_str()).0);
+                                                date_filter.as_ref()
+                                                    .map(|filter| filter.is_match(value.as_ref().map(String::as_str), &self.calendar))
+                                                    .unwrap_or(false)
+                                            } else if *key == issue.progress_attr_key && progress_filter.is_some() {
+                                                progress_filter.as_ref()
+                                                    .map(|filter| filter.is_match(checklist::checklist_progress(&text)))
+                                                    .unwrap_or(false)
+                                            } else if issue_attrs.is_multi_value_key(key) {
+                                                Find::values_of(&parser, &attrs, key).is_empty()
+                                            } else {
+                                                attrs.iter().any(|&(ref attr_key, ref attr_value)| {
+                                                    attr_key == key && parser.parse_value(attr_value.as_str()).0 == *value
+                                                })
+                                            };
+                                            if !is_match {
                                                 break 'path_chunks;
                                             }
                                         }
                                     }
                                 }
-                                println!("{}", path);
+                                visit(path, path_attrs)?;
                             }
                             break 'path_chunks;
                         }
@@ -154,6 +371,89 @@ impl Find {
         }
         Ok(())
     }
+
+    pub fn issue_id(&self) -> Option<&String> {
+        self.issue.as_ref()
+            .and_then(|issue| issue.get_id())
+            .filter(|id| !id.is_empty())
+    }
+
+    pub fn issues_root(&self, path: &str) -> PathBuf {
+        let issues_dir = OsStr::new(self.attrs.attr_value_as_str(FindAttr::IssuesDir.key()));
+        let mut root = PathBuf::new();
+        if !issues_dir.is_empty() || self.trash {
+            let mut prev_chunk = OsStr::new("");
+            for chunk in Path::new(path).iter() {
+                root.push(chunk);
+                if self.is_root_chunk(issues_dir, prev_chunk, chunk) {
+                    break;
+                }
+                prev_chunk = chunk;
+            }
+        }
+        root
+    }
+
+    pub fn load_issue(&self, path: &str, path_attrs: Vec<(String, String)>) -> Result<IssueFile, Error> {
+        let parser = self.attr_parser();
+        let content_attrs = parser.read_attrs(File::open(path)?)?;
+
+        let mut issue = self.issue.clone().unwrap_or_default();
+        issue.attrs.clear_values();
+        for (key, value) in path_attrs.iter().chain(content_attrs.iter()) {
+            let value = parser.parse_value(value.as_str()).0;
+            let exist_values = issue.get_values(key);
+            if issue.attrs.is_multi_value_key(key) && !exist_values.is_empty() {
+                let mut values = exist_values;
+                values.extend(Attrs::split_values(&value));
+                issue.attrs.set_attr_value(key, Attrs::join_values(&values));
+            } else {
+                issue.attrs.set_attr_value(key, value);
+            }
+        }
+
+        let mut issue_file = IssueFile {
+            path: path.to_string(),
+            path_attrs,
+            issue,
+        };
+        // The body is left unread when only headers are scanned
+        if !self.is_header_limited() {
+            issue_file.load_content()?;
+        }
+        Ok(issue_file)
+    }
+
+    pub fn find_issue_files(&self) -> Result<Vec<IssueFile>, Error> {
+        let mut issue_files = Vec::new();
+        self.walk_through_issues(Path::new("."), |path, path_attrs| {
+            issue_files.push(self.load_issue(path, path_attrs)?);
+            Ok(())
+        })?;
+
+        if self.ready() {
+            let all_issues: Vec<Issue<String>> = self.find_all_issue_files()?
+                .into_iter()
+                .map(|issue_file| issue_file.issue)
+                .collect();
+            let deps = Dependencies::from_issues(&all_issues);
+            issue_files.retain(|issue_file| {
+                let issue = &issue_file.issue;
+                !issue.is_closed() && issue.get_id().map(|id| deps.is_ready(id)).unwrap_or(true)
+            });
+        }
+        Ok(issue_files)
+    }
+
+    pub fn find_all_issue_files(&self) -> Result<Vec<IssueFile>, Error> {
+        let mut find = self.clone();
+        find.filter = None;
+        find.attrs.set_attr_value(FindAttr::Ready.key(), false.to_string());
+        if let Some(issue) = find.issue.as_mut() {
+            issue.attrs.clear_values();
+        }
+        find.find_issue_files()
+    }
 }
 
 impl Default for Find {
@@ -167,9 +467,12 @@ impl Default for Find {
         }
 
         Find {
-            issue_attrs: None,
+            issue: None,
             attrs,
             filter: Default::default(),
+            ops: Default::default(),
+            calendar: Default::default(),
+            trash: false,
         }
     }
 }
@@ -187,7 +490,7 @@ impl Command for Find {
             if let FindAttr::Filter = attr {
                 self.filter = Some(
                     Regex::new(&value)
-                        .expect(&format!("Invalid filter regular expression: {}", value)),
+                        .map_err(|_| TodoError::InvalidRegex { value: value.clone() })?,
                 )
             }
             self.attrs.set_attr_value(attr.key(), value);
@@ -201,17 +504,58 @@ impl Command for Find {
         self.attrs.default_key.as_str()
     }
 
-    fn exec(&mut self) {
+    fn exec(&mut self) -> Result<(), Error> {
         let root = Path::new(".");
-        self.walk_through_issues(&root)
-            .expect(&format!("Can't walk through subdir of `{}`", root.display()));
+        if self.attrs.attr_value(FindAttr::Sort.key()).is_some()
+            || self.attrs.attr_value(FindAttr::Columns.key()).is_some()
+            || self.ready()
+        {
+            let mut issue_files = self.find_issue_files()
+                .context(format!("Can't walk through subdir of `{}`", root.display()))?;
+            let columns = Attrs::split_values(self.attrs.attr_value_as_str(FindAttr::Columns.key()));
+            for issue_file in &mut issue_files {
+                if columns.contains(&issue_file.issue.progress_attr_key) {
+                    issue_file.load_content()?;
+                }
+            }
+            self.sort_issue_files(&mut issue_files);
+            for issue_file in &issue_files {
+                println!("{}", self.issue_line(issue_file));
+            }
+        } else {
+            self.walk_through_issues(&root, |path, _| {
+                println!("{}", path);
+                Ok(())
+            }).context(format!("Can't walk through subdir of `{}`", root.display()))?;
+        }
+        Ok(())
     }
 }
 
 impl IssueCommand for Find {
-    fn init_from<T: Content>(&mut self, issue: &Issue<T>) {
-        self.issue_attrs = Some(issue.attrs.clone());
+    fn init_from<T: Content>(&mut self, issue: &Issue<T>) -> Result<(), Error> {
+        self.issue = Some(issue.to_string_issue());
+        Ok(())
     }
 }
 
-impl CanFind for Find {}
\ No newline at end of file
+impl CanFind for Find {
+    fn find_issues(&mut self) -> Result<Vec<Issue<String>>, Error> {
+        let mut issues = Vec::new();
+        for mut issue_file in self.find_issue_files()? {
+            issue_file.load_content()?;
+            issues.push(issue_file.issue);
+        }
+        Ok(issues)
+    }
+
+    fn issue_record(&self, issue: &Issue<String>) -> Result<IssueRecord, Error> {
+        let mut body = issue.content.clone().unwrap_or_default();
+        body.strip_attrs(&AttrParser::new())?;
+        let body = if body.trim().is_empty() { None } else { Some(body) };
+
+        let mut record = IssueRecord::from_issue(issue, body);
+        record.attrs.retain(|&(ref key, _)| key != CreateAttr::Ext.key());
+        Ok(record)
+    }
+}
\ No newline at end of file
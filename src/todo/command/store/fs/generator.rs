@@ -1,15 +1,22 @@
 use failure::Error;
 use std::fs;
 use std::io::{Read, Write};
+use todo::command::store::fs::{Action, Journal};
 use todo::error::TodoError;
 
 #[derive(Clone, Debug, Default)]
 pub struct SequenceGenerator {
     pub required: bool,
     pub file: Option<String>,
+    pub journal: Journal,
+    pub dry_run: bool,
 }
 
 impl SequenceGenerator {
+    pub fn peek(&self) -> Result<String, Error> {
+        SequenceGenerator { dry_run: true, ..self.clone() }.next()
+    }
+
     pub fn next(&self) -> Result<String, Error> {
         match self.file {
             Some(ref path) => {
@@ -31,9 +38,17 @@ impl SequenceGenerator {
                     }
                 };
                 let new_id = format!("{}", id.parse::<u64>()? + 1);
+                if self.dry_run {
+                    return Ok(id);
+                }
 
                 let mut file = fs::File::create(path)?;
                 file.write_all(new_id.as_bytes())?;
+                self.journal.record(Action::Sequence {
+                    file: path.clone(),
+                    old_value: id.clone(),
+                    new_value: new_id,
+                })?;
 
                 Ok(id)
             }
@@ -41,4 +56,27 @@ impl SequenceGenerator {
             _ => Ok("".to_string()),
         }
     }
+
+    pub fn reserve(&self, id: &str) -> Result<(), Error> {
+        let (path, id) = match (self.file.as_ref(), id.parse::<u64>()) {
+            (Some(path), Ok(id)) => (path, id),
+            _ => return Ok(()),
+        };
+        let mut contents = String::new();
+        if let Ok(mut file) = fs::File::open(path) {
+            file.read_to_string(&mut contents)?;
+        }
+        let old_value = contents.trim().to_string();
+        if old_value.parse::<u64>().map(|next| next > id).unwrap_or(false) || self.dry_run {
+            return Ok(());
+        }
+
+        let new_value = format!("{}", id + 1);
+        fs::File::create(path)?.write_all(new_value.as_bytes())?;
+        self.journal.record(Action::Sequence {
+            file: path.clone(),
+            old_value,
+            new_value,
+        })
+    }
 }
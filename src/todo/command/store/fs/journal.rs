@@ -0,0 +1,194 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::process;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use failure::Error;
+use serde_json;
+
+use todo::error::TodoError;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    Create { path: String, content: String },
+    Modify { path: String, old_content: String, new_content: String },
+    Rename { from: String, to: String },
+    Delete { path: String, content: String },
+    Sequence { file: String, old_value: String, new_value: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub operation: String,
+    pub timestamp: String,
+    pub action: Action,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Journal {
+    pub file: Option<String>,
+    pub operation: String,
+    pub timestamp: String,
+    pub dry_run: bool,
+}
+
+fn read_file(path: &str) -> Option<String> {
+    let mut text = String::new();
+    File::open(path).ok()?.read_to_string(&mut text).ok()?;
+    Some(text)
+}
+
+impl Action {
+    pub fn is_safe_to_revert(&self) -> bool {
+        match *self {
+            Action::Create { ref path, ref content } => read_file(path).as_ref() == Some(content),
+            Action::Modify { ref path, ref new_content, .. } => read_file(path).as_ref() == Some(new_content),
+            Action::Rename { ref from, ref to } => Path::new(to).exists() && !Path::new(from).exists(),
+            Action::Delete { ref path, .. } => !Path::new(path).exists(),
+            Action::Sequence { ref file, ref new_value, .. } =>
+                read_file(file).map(|value| value.trim() == new_value.as_str()).unwrap_or(false),
+        }
+    }
+
+    pub fn revert(&self) -> Result<String, Error> {
+        Ok(match *self {
+            Action::Create { ref path, .. } => {
+                fs::remove_file(path)?;
+                format!("removed {}", path)
+            }
+            Action::Modify { ref path, ref old_content, .. } => {
+                File::create(path)?.write_all(old_content.as_bytes())?;
+                format!("restored {}", path)
+            }
+            Action::Rename { ref from, ref to } => {
+                if let Some(dir) = Path::new(from).parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::rename(to, from)?;
+                format!("moved {} back to {}", to, from)
+            }
+            Action::Delete { ref path, ref content } => {
+                if let Some(dir) = Path::new(path).parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                File::create(path)?.write_all(content.as_bytes())?;
+                format!("recreated {}", path)
+            }
+            Action::Sequence { ref file, ref old_value, .. } => {
+                File::create(file)?.write_all(old_value.as_bytes())?;
+                format!("reset {} to {}", file, old_value)
+            }
+        })
+    }
+
+    pub fn describe(&self) -> String {
+        match *self {
+            Action::Create { ref path, .. } => format!("remove {}", path),
+            Action::Modify { ref path, .. } => format!("restore {}", path),
+            Action::Rename { ref from, ref to } => format!("move {} back to {}", to, from),
+            Action::Delete { ref path, .. } => format!("recreate {}", path),
+            Action::Sequence { ref file, ref old_value, .. } => format!("reset {} to {}", file, old_value),
+        }
+    }
+
+    fn path(&self) -> &str {
+        match *self {
+            Action::Create { ref path, .. } | Action::Modify { ref path, .. } | Action::Delete { ref path, .. } => path,
+            Action::Rename { ref to, .. } => to,
+            Action::Sequence { ref file, .. } => file,
+        }
+    }
+}
+
+impl Journal {
+    // A pid alone is reused by later processes, so the start time keeps operations apart
+    pub fn new_operation() -> String {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default();
+        format!("{}-{}", process::id(), nanos)
+    }
+
+    pub fn record(&self, action: Action) -> Result<(), Error> {
+        if let Some(ref path) = self.file {
+            let entry = Entry {
+                operation: self.operation.clone(),
+                timestamp: self.timestamp.clone(),
+                action,
+            };
+            let mut line = serde_json::to_string(&entry)?;
+            line.push('\n');
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)?
+                .write_all(line.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn read_entries(&self) -> Result<Vec<Entry>, Error> {
+        let text = self.file.as_ref().and_then(|path| read_file(path)).unwrap_or_default();
+        let mut entries = Vec::new();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            entries.push(serde_json::from_str(line)?);
+        }
+        Ok(entries)
+    }
+
+    pub fn write_entries(&self, entries: &[Entry]) -> Result<(), Error> {
+        if let Some(ref path) = self.file {
+            let mut text = String::new();
+            for entry in entries {
+                text += &serde_json::to_string(entry)?;
+                text.push('\n');
+            }
+            File::create(path)?.write_all(text.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn undo_last(&self) -> Result<Vec<String>, Error> {
+        let mut entries = self.read_entries()?;
+        let operation = match entries.last() {
+            Some(entry) => entry.operation.clone(),
+            None => return Ok(Vec::new()),
+        };
+        let split_pos = entries.iter()
+            .rposition(|entry| entry.operation != operation)
+            .map(|pos| pos + 1)
+            .unwrap_or(0);
+        let undone = entries.split_off(split_pos);
+
+        let mut messages = Vec::new();
+        let mut reverts = Vec::new();
+        let mut checked_paths = Vec::new();
+        for entry in undone.iter().rev() {
+            let action = &entry.action;
+            // Only the latest action on a path can be checked against the current file state
+            let is_safe = checked_paths.contains(&action.path()) || action.is_safe_to_revert();
+            checked_paths.push(action.path());
+            match *action {
+                Action::Sequence { ref file, .. } if !is_safe => messages.push(format!("kept {}", file)),
+                _ if !is_safe => return Err(TodoError::UnsafeUndo { path: action.path().to_string() }.into()),
+                _ => reverts.push(action),
+            }
+        }
+
+        if self.dry_run {
+            let planned = reverts.iter().map(|action| format!("would {}", action.describe()));
+            return Ok(planned.chain(messages).collect());
+        }
+
+        let mut reverted = Vec::new();
+        for action in reverts {
+            reverted.push(action.revert()?);
+        }
+        reverted.extend(messages);
+        self.write_entries(&entries)?;
+        Ok(reverted)
+    }
+}
@@ -0,0 +1,258 @@
+use std::fs;
+use std::io::Write;
+use std::mem;
+use std::path::{Path, PathBuf};
+use failure::Error;
+use regex;
+
+use todo::command::store::fs::{Action, AttrsContent, AttrsStyle, Find, FindAttr, Format, Journal, Move};
+use todo::command::{Command, IssueCommand};
+use todo::error::TodoError;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Migrate {
+    pub find: Option<Find>,
+    pub issue: Issue<String>,
+    pub from_format: Option<String>,
+    pub to_format: Option<String>,
+    pub attrs_style: String,
+    pub journal: Journal,
+    pub dry_run: bool,
+}
+
+struct Migration {
+    root: PathBuf,
+    path: String,
+    new_path: PathBuf,
+    content: String,
+    new_content: String,
+}
+
+impl Migration {
+    fn print_content_diff(&self) {
+        let lines: Vec<&str> = self.content.lines().collect();
+        let new_lines: Vec<&str> = self.new_content.lines().collect();
+        for line in lines.iter().filter(|line| !new_lines.contains(line)) {
+            println!("  - {}", line);
+        }
+        for line in new_lines.iter().filter(|line| !lines.contains(line)) {
+            println!("  + {}", line);
+        }
+    }
+}
+
+impl Migrate {
+    pub const FROM_FORMAT_PARAM: &'static str = "from-format";
+    pub const TO_FORMAT_PARAM: &'static str = "to-format";
+    const EXT_KEY: &'static str = "ext";
+
+    fn is_key(text: &str) -> bool {
+        !text.is_empty() && text.chars().all(|ch| ch.is_alphanumeric() || ch == '_')
+    }
+
+    /// Splits format into `(text, before, key, after)` placeholders and the trailing text
+    fn placeholders(format: &str) -> (Vec<(&str, &str, &str, &str)>, &str) {
+        let mut placeholders = Vec::new();
+        let mut rest = format;
+        while let Some(start) = rest.find('{') {
+            let end = match rest[start..].find('}') {
+                Some(len) => start + len,
+                None => break,
+            };
+            let parts: Vec<&str> = rest[(start + 1)..end].split(':').collect();
+            let (before, key, after) = match parts.len() {
+                1 => ("", parts[0], ""),
+                2 if !Self::is_key(parts[0]) && Self::is_key(parts[1]) => (parts[0], parts[1], ""),
+                2 => ("", parts[0], parts[1]),
+                _ => (parts[0], parts[1], parts[2]),
+            };
+            placeholders.push((&rest[..start], before, key, after));
+            rest = &rest[(end + 1)..];
+        }
+        (placeholders, rest)
+    }
+
+    pub fn capture_from_format(format: &str) -> String {
+        let (placeholders, rest) = Self::placeholders(format);
+        let mut capture = String::from("^");
+        for (text, before, key, after) in placeholders {
+            let pattern = if after.contains('/') { ".+" } else { "[^/.]+" };
+            capture += &regex::escape(text);
+            capture += &format!(
+                "(?:{}(?P<{}>{}){})??",
+                regex::escape(before),
+                key,
+                pattern,
+                regex::escape(after)
+            );
+        }
+        capture += &regex::escape(rest);
+        capture.push('$');
+        capture
+    }
+
+    fn plan(&self, find: &Find, to_format: &str) -> Result<Vec<Migration>, Error> {
+        let style = AttrsStyle::by_key(&self.attrs_style)
+            .ok_or_else(|| TodoError::UnsupportedAttrsStyle { style: self.attrs_style.clone() })?;
+        let parser = find.attr_parser();
+
+        let mut migrations: Vec<Migration> = Vec::new();
+        for mut issue_file in find.find_issue_files()? {
+            if issue_file.path_attrs.is_empty() {
+                println!("skipped {}: path doesn't match the capture regex", issue_file.path);
+                continue;
+            }
+            issue_file.load_content()?;
+
+            let mut relative_path = to_format.to_string();
+            let mut moved_attrs = Vec::new();
+            for &(ref key, ref value) in &issue_file.path_attrs {
+                if !relative_path.key_replace(key, value) && !value.is_empty() && key != Self::EXT_KEY {
+                    moved_attrs.push((key.clone(), value.clone()));
+                }
+            }
+
+            let content = issue_file.issue.content.clone().unwrap_or_default();
+            let content_attrs = parser.read_attrs(content.as_bytes())?;
+            let mut inlined_attrs = Vec::new();
+            for (_, _, key, _) in Self::placeholders(to_format).0 {
+                if issue_file.path_attrs.iter().any(|attr| attr.0 == key) {
+                    continue;
+                }
+                match content_attrs.iter().find(|attr| attr.0 == key) {
+                    Some(&(_, ref value)) if relative_path.key_replace(key, value) => {
+                        inlined_attrs.push((key.to_string(), value.clone()));
+                    }
+                    _ => {
+                        relative_path.key_replace(key, "");
+                    }
+                }
+            }
+
+            let mut new_content = content.clone();
+            moved_attrs.retain(|&(ref key, _)| content_attrs.iter().all(|&(ref attr_key, _)| attr_key != key));
+            for &(ref key, _) in &inlined_attrs {
+                new_content.remove_attr(&parser, key)?;
+            }
+            for &(ref key, ref value) in &moved_attrs {
+                new_content.set_attr(&parser, style, key, value)?;
+            }
+
+            let root = find.issues_root(&issue_file.path);
+            let new_path = root.join(relative_path);
+            if new_path == Path::new(&issue_file.path) && new_content == content {
+                continue;
+            }
+            let is_taken = migrations.iter().any(|migration| migration.new_path == new_path)
+                || (new_path != Path::new(&issue_file.path) && new_path.exists());
+            if is_taken {
+                return Err(TodoError::FileAlreadyExists { path: new_path.to_string_lossy().to_string() }.into());
+            }
+
+            migrations.push(Migration {
+                root,
+                path: issue_file.path,
+                new_path,
+                content,
+                new_content,
+            });
+        }
+        Ok(migrations)
+    }
+
+    fn apply(&self, migration: &Migration) -> Result<(), Error> {
+        let new_path = migration.new_path.to_string_lossy().to_string();
+        if new_path != migration.path {
+            Move::move_file(&self.journal, &migration.root, &migration.path, &migration.new_path)?;
+        }
+        if migration.new_content != migration.content {
+            fs::File::create(&new_path)?.write_all(migration.new_content.as_bytes())?;
+            self.journal.record(Action::Modify {
+                path: new_path.clone(),
+                old_content: migration.content.clone(),
+                new_content: migration.new_content.clone(),
+            })?;
+        }
+        println!("{}", new_path);
+        Ok(())
+    }
+}
+
+impl Command for Migrate {
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if param == Self::FROM_FORMAT_PARAM {
+            self.from_format = Some(value);
+        } else if param == Self::TO_FORMAT_PARAM {
+            self.to_format = Some(value);
+        } else if let Some(find) = self.find.as_mut() {
+            let key = if param.is_empty() {
+                find.default_param_key().to_string()
+            } else {
+                param.to_string()
+            };
+            find.set_param(&key, value)?;
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.find
+            .as_ref()
+            .map(|find| find.default_param_key())
+            .expect("Find command not exist")
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        let to_format = self.to_format.clone()
+            .ok_or_else(|| TodoError::ParamNotSpecified { param: Self::TO_FORMAT_PARAM.to_string() })?;
+        if let Some(ref from_format) = self.from_format {
+            find.set_param(FindAttr::Capture.key(), Self::capture_from_format(from_format))?;
+        }
+        find.init_from(&self.issue)?;
+
+        for migration in self.plan(&find, &to_format)? {
+            if self.dry_run {
+                println!("would move {} to {}", migration.path, migration.new_path.to_string_lossy());
+                migration.print_content_diff();
+            } else {
+                self.apply(&migration)?;
+            }
+        }
+        self.find = Some(find);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    #[test]
+    fn capture_from_format() {
+        let capture = Migrate::capture_from_format("{scope:/}{priority:.}{id:.}{name}{.:ext}");
+        let regex = Regex::new(&capture).unwrap();
+
+        let caps = regex.captures("back/end/A.12.task.md").unwrap();
+        assert_eq!("back/end", &caps["scope"]);
+        assert_eq!("A", &caps["priority"]);
+        assert_eq!("12", &caps["id"]);
+        assert_eq!("task", &caps["name"]);
+        assert_eq!("md", &caps["ext"]);
+
+        let caps = regex.captures("task.md").unwrap();
+        assert!(caps.name("scope").is_none());
+        assert!(caps.name("priority").is_none());
+        assert_eq!("task", &caps["name"]);
+        assert_eq!("md", &caps["ext"]);
+
+        let capture = Migrate::capture_from_format("{id}-{name}.txt");
+        let caps = Regex::new(&capture).unwrap().captures("12-task.txt").unwrap();
+        assert_eq!("12", &caps["id"]);
+        assert_eq!("task", &caps["name"]);
+    }
+}
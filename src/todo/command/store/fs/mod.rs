@@ -1,13 +1,29 @@
 pub mod content;
 pub mod create;
+pub mod edit;
 pub mod find;
 pub mod format;
 pub mod generator;
+pub mod journal;
+pub mod migrate;
+pub mod mv;
 pub mod parser;
+pub mod restore;
+pub mod rm;
+pub mod undo;
+pub mod update;
 
 pub use self::content::*;
 pub use self::create::*;
+pub use self::edit::*;
 pub use self::find::*;
 pub use self::format::*;
 pub use self::generator::*;
+pub use self::journal::*;
+pub use self::migrate::*;
+pub use self::mv::*;
 pub use self::parser::*;
+pub use self::restore::*;
+pub use self::rm::*;
+pub use self::undo::*;
+pub use self::update::*;
@@ -0,0 +1,163 @@
+use std::fs;
+use std::mem;
+use std::path::{Component, Path};
+use failure::{Error, ResultExt};
+
+use date::Calendar;
+use todo::command::store::fs::{Action, Find, Format, Journal};
+use todo::command::{Command, IssueCommand};
+use todo::error::TodoError;
+use todo::history::{ChangeRecord, HistoryLog};
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Move {
+    pub find: Option<Find>,
+    pub issue: Issue<String>,
+    pub changes: Vec<(String, String)>,
+    pub format: String,
+    pub calendar: Calendar,
+    pub history: HistoryLog,
+    pub user: Option<String>,
+    pub journal: Journal,
+    pub dry_run: bool,
+}
+
+impl Move {
+    // Only plain names may be used, so the file can't leave the issues root
+    fn is_relative_path(path: &str) -> bool {
+        Path::new(path).components().all(|component| match component {
+            Component::Normal(_) | Component::CurDir => true,
+            _ => false,
+        })
+    }
+
+    pub fn render_path(&self, path_attrs: &[(String, String)]) -> Result<String, TodoError> {
+        let mut attrs = path_attrs.to_vec();
+        for &(ref key, ref value) in &self.changes {
+            if !Self::is_relative_path(value) {
+                return Err(TodoError::InvalidPathValue { value: value.clone() });
+            }
+            match attrs.iter_mut().find(|attr| attr.0 == *key) {
+                Some(attr) => attr.1 = value.clone(),
+                None => return Err(TodoError::UnknownCommandParam { param: key.clone() }),
+            }
+        }
+
+        let mut path = self.format.clone();
+        for (key, value) in attrs {
+            path.key_replace(&key, &value);
+        }
+        if !Self::is_relative_path(&path) {
+            return Err(TodoError::InvalidPathValue { value: path });
+        }
+        Ok(path)
+    }
+
+    pub fn change_records(&self, id: &str, path_attrs: &[(String, String)]) -> Vec<ChangeRecord> {
+        let timestamp = self.calendar.timestamp();
+        let user = self.user.clone().unwrap_or_default();
+        let id = self.changes.iter()
+            .rev()
+            .find(|&&(ref key, _)| *key == self.issue.id_attr_key)
+            .map(|&(_, ref new)| new.as_str())
+            .unwrap_or(id);
+
+        let mut records: Vec<ChangeRecord> = Vec::new();
+        for &(ref key, ref new) in &self.changes {
+            let old = path_attrs.iter()
+                .find(|attr| attr.0 == *key)
+                .map(|attr| attr.1.clone())
+                .unwrap_or_default();
+            records.retain(|record| record.key != *key);
+            if old != *new {
+                records.push(ChangeRecord {
+                    timestamp: timestamp.clone(),
+                    user: user.clone(),
+                    id: id.to_string(),
+                    key: key.clone(),
+                    old,
+                    new: new.clone(),
+                });
+            }
+        }
+        records
+    }
+
+    pub fn move_file(journal: &Journal, root: &Path, from: &str, to: &Path) -> Result<(), Error> {
+        let to_str = to.to_string_lossy().to_string();
+        if let Some(dir) = to.parent() {
+            fs::create_dir_all(dir).context(format!("Can't create dir: {:?}", dir))?;
+        }
+        fs::rename(from, to).context(format!("Can't move {} to {}", from, to_str))?;
+        journal.record(Action::Rename { from: from.to_string(), to: to_str })?;
+        Self::remove_empty_dirs(root, Path::new(from));
+        Ok(())
+    }
+
+    pub fn remove_empty_dirs(root: &Path, path: &Path) {
+        let mut dir = path.parent();
+        while let Some(current) = dir {
+            if current == root || !current.starts_with(root) || fs::remove_dir(current).is_err() {
+                break;
+            }
+            dir = current.parent();
+        }
+    }
+}
+
+impl Command for Move {
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            let key = self.issue.attrs
+                .key_by_alias(param.to_lowercase().as_str())
+                .map(|key| (*key).clone())
+                .unwrap_or_else(|| param.to_lowercase());
+            self.changes.push((key, value));
+        } else if self.issue.get_id().is_none() {
+            let id_attr_key = self.issue.id_attr_key.clone();
+            self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        } else {
+            let default_key = (*self.issue.attrs.default_key).clone();
+            self.changes.push((default_key, value));
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        let id = self.issue.required_id()?.clone();
+        find.init_from(&self.issue)?;
+        let issue_file = find.find_issue_files()?
+            .into_iter()
+            .next()
+            .ok_or_else(|| TodoError::IssueNotFound { id: id.clone() })?;
+
+        let root = find.issues_root(&issue_file.path);
+        let new_path = root.join(self.render_path(&issue_file.path_attrs)?);
+        let new_path_str = new_path.to_string_lossy().to_string();
+        if new_path_str == issue_file.path {
+            self.find = Some(find);
+            return Ok(());
+        }
+        if new_path.exists() {
+            return Err(TodoError::FileAlreadyExists { path: new_path_str }.into());
+        }
+
+        if self.dry_run {
+            println!("would move {} to {}", issue_file.path, new_path_str);
+        } else {
+            Self::move_file(&self.journal, &root, &issue_file.path, &new_path)?;
+            self.history.append(&self.change_records(&id, &issue_file.path_attrs))?;
+            println!("{}", new_path_str);
+        }
+        self.find = Some(find);
+        Ok(())
+    }
+}
@@ -5,20 +5,76 @@ use regex::Regex;
 
 use todo::attrs::Attrs;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AttrsStyle {
+    Attr,
+    Yaml,
+    Toml,
+}
+
+impl AttrsStyle {
+    pub fn by_key(key: &str) -> Option<Self> {
+        Some(match key {
+            key if AttrsStyle::Attr.key() == key => AttrsStyle::Attr,
+            key if AttrsStyle::Yaml.key() == key => AttrsStyle::Yaml,
+            key if AttrsStyle::Toml.key() == key => AttrsStyle::Toml,
+            _ => return None,
+        })
+    }
+
+    pub fn by_fence(fence: &str) -> Option<Self> {
+        Some(match fence {
+            fence if AttrsStyle::Yaml.fence() == fence => AttrsStyle::Yaml,
+            fence if AttrsStyle::Toml.fence() == fence => AttrsStyle::Toml,
+            _ => return None,
+        })
+    }
+
+    pub fn key(&self) -> &'static str {
+        match *self {
+            AttrsStyle::Attr => "attr",
+            AttrsStyle::Yaml => "yaml",
+            AttrsStyle::Toml => "toml",
+        }
+    }
+
+    pub fn fence(&self) -> &'static str {
+        match *self {
+            AttrsStyle::Attr => "",
+            AttrsStyle::Yaml => "---",
+            AttrsStyle::Toml => "+++",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttrSpan {
+    pub key: String,
+    pub value: String,
+    pub style: AttrsStyle,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Clone, Debug)]
 pub struct AttrParser {
     pub attr_regex: Regex,
     pub expr_regex: Regex,
+    pub header_only: bool,
+    pub lines_limit: Option<usize>,
 }
 
 impl AttrParser {
     pub fn new() -> Self {
-        let attr_regex = r"^\#\[(?s)(?P<key>.+):(?P<value>.*)\]$";
+        let attr_regex = r"^\#\[(?s)(?P<key>.+?):(?P<value>.*)\]$";
         let expr_regex = r"^(?s)(?P<actual_value>.*)\s=\s(?P<expr>if\s.+)$";
         AttrParser {
             attr_regex: Regex::new(attr_regex)
                 .expect(&format!("`{}` is not regular expression", attr_regex)),
             expr_regex: Regex::new(expr_regex)
                 .expect(&format!("`{}` is not regular expression", expr_regex)),
+            header_only: false,
+            lines_limit: None,
         }
     }
 
@@ -30,6 +86,71 @@ impl AttrParser {
         format!("#[{}: {}]", key.as_ref(), value.as_ref())
     }
 
+    pub fn encode_front_matter_attr<K, V>(style: AttrsStyle, key: K, value: V) -> String
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let (key, value) = (key.as_ref(), value.as_ref());
+        match style {
+            AttrsStyle::Attr => AttrParser::encode_attr(key, value),
+            AttrsStyle::Yaml => {
+                let key = if AttrParser::is_plain_yaml(key) && !key.contains(':') {
+                    key.to_string()
+                } else {
+                    AttrParser::quote(key)
+                };
+                if value.is_empty() {
+                    format!("{}:", key)
+                } else if AttrParser::is_plain_yaml(value) {
+                    format!("{}: {}", key, value)
+                } else {
+                    format!("{}: {}", key, AttrParser::quote(value))
+                }
+            }
+            AttrsStyle::Toml => {
+                let bare = !key.is_empty()
+                    && key.chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+                let key = if bare { key.to_string() } else { AttrParser::quote(key) };
+                format!("{} = {}", key, AttrParser::quote(value))
+            }
+        }
+    }
+
+    fn is_plain_yaml(text: &str) -> bool {
+        !text.is_empty()
+            && text.trim() == text
+            && !text.contains(": ")
+            && !text.contains(" #")
+            && !text.ends_with(':')
+            && !text.starts_with(|ch: char| "\"'[]{}#&*!|>%@`,-?:".contains(ch))
+    }
+
+    fn quote(text: &str) -> String {
+        format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+
+    pub fn encode_attrs<K, V>(style: AttrsStyle, attrs: &[(K, V)]) -> String
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut content = String::new();
+        if attrs.is_empty() {
+            return content;
+        }
+        if style != AttrsStyle::Attr {
+            content += &format!("{}\n", style.fence());
+        }
+        for &(ref key, ref value) in attrs {
+            content += &format!("{}\n", AttrParser::encode_front_matter_attr(style, key, value));
+        }
+        if style != AttrsStyle::Attr {
+            content += &format!("{}\n", style.fence());
+        }
+        content
+    }
+
     pub fn parse_attr<L>(&self, line: L) -> Option<(String, String)>
     where
         L: AsRef<str>,
@@ -41,6 +162,59 @@ impl AttrParser {
         }
     }
 
+    pub fn parse_front_matter_attr<L>(&self, style: AttrsStyle, line: L) -> Option<(String, String)>
+    where
+        L: AsRef<str>,
+    {
+        let line = line.as_ref().trim();
+        let separator = match style {
+            AttrsStyle::Attr => return self.parse_attr(line),
+            AttrsStyle::Yaml => ':',
+            AttrsStyle::Toml => '=',
+        };
+        if line.starts_with('#') {
+            return None;
+        }
+        // A quoted key may contain the separator itself
+        let key_end = match line.chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => AttrParser::closing_quote_pos(line, quote)? + 1,
+            _ => 0,
+        };
+        let pos = key_end + line[key_end..].find(separator)?;
+        let key = AttrParser::unquote(line[..pos].trim());
+        if key.is_empty() {
+            return None;
+        }
+        Some((key, AttrParser::unquote(line[(pos + 1)..].trim())))
+    }
+
+    fn closing_quote_pos(text: &str, quote: char) -> Option<usize> {
+        let mut escaped = false;
+        for (pos, ch) in text.char_indices().skip(1) {
+            match ch {
+                _ if escaped => escaped = false,
+                '\\' if quote == '"' => escaped = true,
+                _ if ch == quote => return Some(pos),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn unquote(value: &str) -> String {
+        if value.len() > 1 {
+            if value.starts_with('"') && value.ends_with('"') {
+                return value[1..(value.len() - 1)]
+                    .replace("\\\"", "\"")
+                    .replace("\\\\", "\\");
+            }
+            if value.starts_with('\'') && value.ends_with('\'') {
+                return value[1..(value.len() - 1)].to_string();
+            }
+        }
+        value.to_string()
+    }
+
     #[allow(dead_code)]
     pub fn parse_and_set_attr<L>(&self, line: L, attrs: &mut Attrs) -> Option<String>
     where
@@ -67,15 +241,59 @@ impl AttrParser {
     where
         R: Read,
     {
-        let mut attrs = Vec::new();
+        Ok(self.read_attr_spans(source)?
+            .into_iter()
+            .map(|span| (span.key, span.value))
+            .collect())
+    }
+
+    pub fn read_attr_spans<R>(&self, source: R) -> Result<Vec<AttrSpan>, Error>
+    where
+        R: Read,
+    {
+        let mut spans = Vec::new();
 
         let mut reader = BufReader::new(source);
         let mut buf = Vec::<u8>::new();
         let mut attr = String::new();
+        let mut attr_start = 0;
         let mut open_brackets = 0;
+        let mut lines_count = 0;
+        let mut offset = 0;
+        let mut front_matter = None;
 
         while reader.read_until(b'\n', &mut buf)? != 0 {
-            if buf.starts_with(&[b'#', b'[']) || !attr.is_empty() {
+            let line_start = offset;
+            offset += buf.len();
+            lines_count += 1;
+            if self.lines_limit.map(|limit| lines_count > limit).unwrap_or(false) {
+                break;
+            }
+            if lines_count == 1 {
+                front_matter = str::from_utf8(&buf).ok().and_then(|line| AttrsStyle::by_fence(line.trim()));
+                if front_matter.is_some() {
+                    buf.clear();
+                    continue;
+                }
+            }
+            if let Some(style) = front_matter {
+                let line = str::from_utf8(&buf)?;
+                if line.trim() == style.fence() {
+                    front_matter = None;
+                } else if let Some((key, value)) = self.parse_front_matter_attr(style, line) {
+                    let len = line.trim_end_matches(|ch| ch == '\r' || ch == '\n').len();
+                    spans.push(AttrSpan {
+                        key,
+                        value,
+                        style,
+                        start: line_start,
+                        end: line_start + len,
+                    });
+                }
+            } else if buf.starts_with(&[b'#', b'[']) || !attr.is_empty() {
+                if attr.is_empty() {
+                    attr_start = line_start;
+                }
                 let mut in_progress = true;
                 for (i, &bch) in buf.iter().enumerate() {
                     match bch {
@@ -87,8 +305,15 @@ impl AttrParser {
 
                             if open_brackets == 0 {
                                 attr += str::from_utf8(&buf[..(i + 1)])?;
-                                let _ = self.parse_attr(&attr)
-                                    .map(|parsed_attr| attrs.push(parsed_attr));
+                                if let Some((key, value)) = self.parse_attr(&attr) {
+                                    spans.push(AttrSpan {
+                                        key,
+                                        value,
+                                        style: AttrsStyle::Attr,
+                                        start: attr_start,
+                                        end: line_start + i + 1,
+                                    });
+                                }
                                 in_progress = false;
                                 attr.clear();
                                 break;
@@ -100,10 +325,26 @@ impl AttrParser {
                 if in_progress {
                     attr += str::from_utf8(&buf)?;
                 }
+            } else if self.header_only && !buf.iter().all(u8::is_ascii_whitespace) {
+                // The body is never decoded, so it may hold any bytes
+                break;
             }
             buf.clear();
         }
-        Ok(attrs)
+        Ok(spans)
+    }
+
+    pub fn front_matter_end(&self, text: &str) -> Option<(AttrsStyle, usize)> {
+        let mut lines = text.split('\n');
+        let style = AttrsStyle::by_fence(lines.next()?.trim())?;
+        let mut offset = text.find('\n')? + 1;
+        for line in lines {
+            if line.trim() == style.fence() {
+                return Some((style, offset));
+            }
+            offset += line.len() + 1;
+        }
+        None
     }
 }
 
@@ -122,6 +363,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encode_attrs() {
+        let attrs = [("key", "value"), ("key 2", "value: 2"), ("empty", "")];
+
+        assert_eq!("", AttrParser::encode_attrs::<&str, &str>(AttrsStyle::Yaml, &[]));
+        assert_eq!(
+            "#[key: value]\n#[key 2: value: 2]\n#[empty: ]\n",
+            AttrParser::encode_attrs(AttrsStyle::Attr, &attrs)
+        );
+        assert_eq!(
+            "---\nkey: value\nkey 2: \"value: 2\"\nempty:\n---\n",
+            AttrParser::encode_attrs(AttrsStyle::Yaml, &attrs)
+        );
+        assert_eq!(
+            "+++\nkey = \"value\"\n\"key 2\" = \"value: 2\"\nempty = \"\"\n+++\n",
+            AttrParser::encode_attrs(AttrsStyle::Toml, &attrs)
+        );
+        assert_eq!(
+            "key = \"say \\\"hi\\\"\"",
+            AttrParser::encode_front_matter_attr(AttrsStyle::Toml, "key", "say \"hi\"")
+        );
+        assert_eq!(
+            "\"a:b\": value",
+            AttrParser::encode_front_matter_attr(AttrsStyle::Yaml, "a:b", "value")
+        );
+        assert_eq!(
+            "\"a = b\" = \"value\"",
+            AttrParser::encode_front_matter_attr(AttrsStyle::Toml, "a = b", "value")
+        );
+    }
+
+    #[test]
+    fn parse_front_matter_attr() {
+        let parser = AttrParser::new();
+
+        assert_eq!(None, parser.parse_front_matter_attr(AttrsStyle::Yaml, "test"));
+        assert_eq!(None, parser.parse_front_matter_attr(AttrsStyle::Yaml, "# key: value"));
+        assert_eq!(None, parser.parse_front_matter_attr(AttrsStyle::Toml, "key: value"));
+        assert_eq!(
+            Some(("key", "value").to_strings()),
+            parser.parse_front_matter_attr(AttrsStyle::Yaml, "key: value")
+        );
+        assert_eq!(
+            Some(("key", "value: 2").to_strings()),
+            parser.parse_front_matter_attr(AttrsStyle::Yaml, " key : \"value: 2\"\n")
+        );
+        assert_eq!(
+            Some(("key", "value").to_strings()),
+            parser.parse_front_matter_attr(AttrsStyle::Toml, "key = 'value'")
+        );
+        assert_eq!(
+            Some(("key", "say \"hi\"").to_strings()),
+            parser.parse_front_matter_attr(AttrsStyle::Toml, "\"key\" = \"say \\\"hi\\\"\"")
+        );
+        assert_eq!(
+            Some(("a = b", "value").to_strings()),
+            parser.parse_front_matter_attr(AttrsStyle::Toml, "\"a = b\" = \"value\"")
+        );
+        assert_eq!(
+            Some(("a:b", "value").to_strings()),
+            parser.parse_front_matter_attr(AttrsStyle::Yaml, "\"a:b\": value")
+        );
+        assert_eq!(
+            Some(("key", "value").to_strings()),
+            parser.parse_front_matter_attr(AttrsStyle::Attr, "#[key: value]")
+        );
+    }
+
     #[test]
     fn parse_attr() {
         let parser = AttrParser::new();
@@ -157,6 +466,10 @@ mod tests {
             Some(("key 1", "[value 1, value 2]").to_strings()),
             parser.parse_attr("#[key 1: [value 1, value 2]]")
         );
+        assert_eq!(
+            Some(("time", "2018-06-04 10:00:00").to_strings()),
+            parser.parse_attr("#[time: 2018-06-04 10:00:00]")
+        );
     }
 
     #[test]
@@ -303,4 +616,87 @@ test
             attrs
         );
     }
+
+    #[test]
+    fn read_header_attrs() {
+        let mut parser = AttrParser::new();
+        parser.header_only = true;
+
+        let source = r#"
+#[key: value]
+
+#[key 2:
+value 2]
+text
+#[key 3: value 3]
+        "#;
+        let attrs = parser
+            .read_attrs(source.as_bytes())
+            .expect("Read attrs error");
+        assert_eq!([("key", "value"), ("key 2", "value 2")].to_strings(), attrs);
+
+        let source = "text\n#[key: value]\n";
+        let attrs = parser
+            .read_attrs(source.as_bytes())
+            .expect("Read attrs error");
+        assert!(attrs.is_empty());
+
+        let source: &[u8] = b"#[key: value]\n\xff\xfe\n";
+        let attrs = parser
+            .read_attrs(source)
+            .expect("Read attrs error");
+        assert_eq!([("key", "value")].to_strings(), attrs);
+
+        parser.header_only = false;
+        parser.lines_limit = Some(2);
+
+        let source = "#[key: value]\ntext\n#[key 2: value 2]\n";
+        let attrs = parser
+            .read_attrs(source.as_bytes())
+            .expect("Read attrs error");
+        assert_eq!([("key", "value")].to_strings(), attrs);
+
+        parser.header_only = true;
+
+        let source = "#[key: value]\n#[key 2: value 2]\n#[key 3: value 3]\n";
+        let attrs = parser
+            .read_attrs(source.as_bytes())
+            .expect("Read attrs error");
+        assert_eq!([("key", "value"), ("key 2", "value 2")].to_strings(), attrs);
+    }
+
+    #[test]
+    fn read_front_matter_attrs() {
+        let parser = AttrParser::new();
+
+        let source = "---\nkey: value\nkey 2: \"value 2\"\n---\ntext\n#[key 3: value 3]\n";
+        let attrs = parser
+            .read_attrs(source.as_bytes())
+            .expect("Read attrs error");
+        assert_eq!(
+            [("key", "value"), ("key 2", "value 2"), ("key 3", "value 3")].to_strings(),
+            attrs
+        );
+
+        let source = "+++\nkey = \"value\"\n+++\n---\nkey 2: value 2\n---\n";
+        let attrs = parser
+            .read_attrs(source.as_bytes())
+            .expect("Read attrs error");
+        assert_eq!([("key", "value")].to_strings(), attrs);
+
+        let source = "\n---\nkey: value\n---\n";
+        let attrs = parser
+            .read_attrs(source.as_bytes())
+            .expect("Read attrs error");
+        assert!(attrs.is_empty());
+
+        let mut parser = AttrParser::new();
+        parser.header_only = true;
+
+        let source = "---\nkey: value\n---\n\n#[key 2: value 2]\ntext\n#[key 3: value 3]\n";
+        let attrs = parser
+            .read_attrs(source.as_bytes())
+            .expect("Read attrs error");
+        assert_eq!([("key", "value"), ("key 2", "value 2")].to_strings(), attrs);
+    }
 }
@@ -0,0 +1,64 @@
+use std::mem;
+use std::path::Path;
+use failure::Error;
+
+use todo::command::store::fs::{Find, Journal, Move};
+use todo::command::{Command, IssueCommand};
+use todo::error::TodoError;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Restore {
+    pub find: Option<Find>,
+    pub issue: Issue<String>,
+    pub journal: Journal,
+    pub dry_run: bool,
+}
+
+impl Command for Restore {
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            return Err(TodoError::UnknownCommandParam { param: param.to_string() });
+        }
+        let id_attr_key = self.issue.id_attr_key.clone();
+        self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        let id = self.issue.required_id()?.clone();
+        find.trash = true;
+        find.init_from(&self.issue)?;
+        let issue_file = find.find_issue_files()?
+            .into_iter()
+            .next()
+            .ok_or(TodoError::IssueNotFound { id })?;
+
+        let trash_root = find.issues_root(&issue_file.path);
+        let path = Path::new(&issue_file.path);
+        let restored_path = trash_root
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(path.strip_prefix(&trash_root)?);
+        let restored_path_str = restored_path.to_string_lossy().to_string();
+        if restored_path.exists() {
+            return Err(TodoError::FileAlreadyExists { path: restored_path_str }.into());
+        }
+
+        if self.dry_run {
+            println!("would move {} to {}", issue_file.path, restored_path_str);
+        } else {
+            Move::move_file(&self.journal, &trash_root, &issue_file.path, &restored_path)?;
+            println!("{}", restored_path_str);
+        }
+        self.find = Some(find);
+        Ok(())
+    }
+}
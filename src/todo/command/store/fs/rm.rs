@@ -0,0 +1,81 @@
+use std::fs;
+use std::mem;
+use std::path::Path;
+use failure::{Error, ResultExt};
+
+use todo::command::store::fs::{Action, Find, Journal, Move};
+use todo::command::{Command, IssueCommand};
+use todo::error::TodoError;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Remove {
+    pub find: Option<Find>,
+    pub issue: Issue<String>,
+    pub purge: bool,
+    pub journal: Journal,
+    pub dry_run: bool,
+}
+
+impl Remove {
+    pub const PURGE_PARAM: &'static str = "purge";
+}
+
+impl Command for Remove {
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if param == Self::PURGE_PARAM {
+            self.purge = value == "true";
+        } else if !param.is_empty() {
+            return Err(TodoError::UnknownCommandParam { param: param.to_string() });
+        } else {
+            let id_attr_key = self.issue.id_attr_key.clone();
+            self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        let id = self.issue.required_id()?.clone();
+        find.init_from(&self.issue)?;
+        let mut issue_file = find.find_issue_files()?
+            .into_iter()
+            .next()
+            .ok_or(TodoError::IssueNotFound { id })?;
+        issue_file.load_content()?;
+
+        let root = find.issues_root(&issue_file.path);
+        let path = &issue_file.path;
+        if self.purge {
+            if self.dry_run {
+                println!("would delete {}", path);
+            } else {
+                fs::remove_file(path).context(format!("Can't delete {}", path))?;
+                let content = issue_file.issue.content.clone().unwrap_or_default();
+                self.journal.record(Action::Delete { path: path.clone(), content })?;
+                Move::remove_empty_dirs(&root, Path::new(path));
+                println!("deleted {}", path);
+            }
+        } else {
+            let trash_path = root.join(Find::TRASH_DIR).join(Path::new(path).strip_prefix(&root)?);
+            let trash_path_str = trash_path.to_string_lossy().to_string();
+            if trash_path.exists() {
+                return Err(TodoError::FileAlreadyExists { path: trash_path_str }.into());
+            }
+            if self.dry_run {
+                println!("would move {} to {}", path, trash_path_str);
+            } else {
+                Move::move_file(&self.journal, &root, path, &trash_path)?;
+                println!("{}", trash_path_str);
+            }
+        }
+        self.find = Some(find);
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use failure::Error;
+
+use todo::command::store::fs::Journal;
+use todo::command::Command;
+use todo::error::TodoError;
+
+#[derive(Clone, Debug, Default)]
+pub struct Undo {
+    pub journal: Journal,
+}
+
+impl Command for Undo {
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        Err(TodoError::UnknownCommandParam {
+            param: if param.is_empty() { value } else { param.to_string() },
+        })
+    }
+
+    fn default_param_key(&self) -> &str {
+        ""
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        for message in self.journal.undo_last()? {
+            println!("{}", message);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+use std::fs;
+use std::io::Write;
+use std::rc::Rc;
+use failure::Error;
+
+use date::Calendar;
+use todo::attrs::Attrs;
+use todo::command::store::fs::{Action, AttrsContent, AttrsStyle, Find, IssueFile, Journal};
+use todo::command::store::{Find as CanFind, Update as CanUpdate};
+use todo::command::{Command, IssueCommand};
+use todo::error::TodoError;
+use todo::exchange::IssueRecord;
+use todo::history::{ChangeRecord, HistoryLog};
+use todo::issue::{Content, Issue};
+
+#[derive(Clone, Debug, Default)]
+pub struct Update {
+    pub attrs: Attrs,
+    pub find: Find,
+    pub updated_attr: Option<String>,
+    pub calendar: Calendar,
+    pub history: HistoryLog,
+    pub user: Option<String>,
+    pub journal: Journal,
+    pub dry_run: bool,
+}
+
+#[derive(PartialEq)]
+pub enum UpdateAttr {
+    AttrsStyle,
+}
+
+impl UpdateAttr {
+    pub fn by_key(key: &str) -> Option<Self> {
+        Some(match key {
+            key if UpdateAttr::AttrsStyle.key() == key => UpdateAttr::AttrsStyle,
+            _ => return None,
+        })
+    }
+
+    pub fn key(&self) -> &'static str {
+        match *self {
+            UpdateAttr::AttrsStyle => "attrs_style",
+        }
+    }
+}
+
+impl Update {
+    pub fn attrs_style(&self) -> Result<AttrsStyle, TodoError> {
+        let style = self.attrs.attr_value_as_str(UpdateAttr::AttrsStyle.key());
+        if style.is_empty() {
+            Ok(AttrsStyle::Attr)
+        } else {
+            AttrsStyle::by_key(style)
+                .ok_or_else(|| TodoError::UnsupportedAttrsStyle { style: style.to_string() })
+        }
+    }
+
+    pub fn changed_attrs(&self, origin: &Issue<String>, issue: &Issue<String>, path_attrs: &[(String, String)]) -> Vec<(String, String, String)> {
+        let mut keys: Vec<Rc<String>> = origin.attrs.keys.clone();
+        for key in &issue.attrs.keys {
+            if !keys.contains(key) {
+                keys.push(key.clone());
+            }
+        }
+
+        let mut changes = Vec::new();
+        for key in keys {
+            let key = key.as_str();
+            let in_path = path_attrs.iter()
+                .any(|&(ref path_key, ref path_value)| path_key == key && !path_value.is_empty());
+            let old = origin.attrs.attr_value_as_str(key);
+            let new = issue.attrs.attr_value_as_str(key);
+            if !in_path && old != new {
+                changes.push((key.to_string(), old.to_string(), new.to_string()));
+            }
+        }
+        changes
+    }
+
+    pub fn write_changes(&self, origin: &Issue<String>, issue: &Issue<String>, path_attrs: &[(String, String)]) -> Result<String, Error> {
+        let parser = self.find.attr_parser();
+        let style = self.attrs_style()?;
+        let mut content = issue.content.clone().unwrap_or_default();
+
+        for (key, _, value) in self.changed_attrs(origin, issue, path_attrs) {
+            if value.is_empty() {
+                content.remove_attr(&parser, &key)?;
+            } else {
+                content.set_attr(&parser, style, &key, &value)?;
+            }
+        }
+        Ok(content)
+    }
+
+    pub fn change_records(&self, origin: &Issue<String>, issue: &Issue<String>, path_attrs: &[(String, String)]) -> Vec<ChangeRecord> {
+        let timestamp = self.calendar.timestamp();
+        let user = self.user.clone().unwrap_or_default();
+        let id = match issue.get_id().or(origin.get_id()) {
+            Some(id) if !id.is_empty() => id.clone(),
+            _ => return Vec::new(),
+        };
+
+        self.changed_attrs(origin, issue, path_attrs)
+            .into_iter()
+            .filter(|&(ref key, _, _)| Some(key) != self.updated_attr.as_ref())
+            .map(|(key, old, new)| ChangeRecord {
+                timestamp: timestamp.clone(),
+                user: user.clone(),
+                id: id.clone(),
+                key,
+                old,
+                new,
+            })
+            .collect()
+    }
+
+    fn print_changes(&self, path: &str, origin: &Issue<String>, issue: &Issue<String>, path_attrs: &[(String, String)]) {
+        let empty_or = |value: String| if value.is_empty() { "-".to_string() } else { value };
+        let changes = self.changed_attrs(origin, issue, path_attrs);
+
+        println!("would update {}", path);
+        if changes.is_empty() {
+            println!("  content changed");
+        }
+        for (key, old, new) in changes {
+            println!("  {}: {} -> {}", key, empty_or(old), empty_or(new));
+        }
+    }
+}
+
+impl Command for Update {
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if let Some(key) = self.attrs.key_by_alias(param.to_lowercase().as_str()) {
+            let attr = UpdateAttr::by_key(key.as_str())
+                .expect(&format!(
+                    "{} command has `{}` key, but not support this attr",
+                    stringify!(Update),
+                    key
+                ));
+
+            self.attrs.set_attr_value(attr.key(), value);
+            Ok(())
+        } else {
+            self.find.set_param(param, value)
+        }
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.find.default_param_key()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        self.update(|_| Ok(()))?;
+        Ok(())
+    }
+}
+
+impl IssueCommand for Update {
+    fn init_from<T: Content>(&mut self, issue: &Issue<T>) -> Result<(), Error> {
+        self.find.init_from(issue)
+    }
+}
+
+impl CanUpdate for Update {
+    fn update<F>(&mut self, mut f: F) -> Result<usize, Error>
+    where
+        F: FnMut(&mut Issue<String>) -> Result<(), Error>,
+    {
+        let issue_files = self.find.find_issue_files()?;
+        if let (true, Some(id)) = (issue_files.is_empty(), self.find.issue_id()) {
+            return Err(TodoError::IssueNotFound { id: id.clone() }.into());
+        }
+
+        let mut count = 0;
+        for mut issue_file in issue_files {
+            issue_file.load_content()?;
+            let IssueFile { path, path_attrs, issue: origin } = issue_file;
+            let mut issue = origin.clone();
+            f(&mut issue)?;
+
+            let mut content = self.write_changes(&origin, &issue, &path_attrs)?;
+            if Some(&content) != origin.content.as_ref() {
+                if let Some(ref key) = self.updated_attr {
+                    issue.attrs.set_attr_value(key.as_str(), self.calendar.timestamp());
+                    content = self.write_changes(&origin, &issue, &path_attrs)?;
+                }
+            }
+            if Some(&content) != origin.content.as_ref() {
+                if self.dry_run {
+                    self.print_changes(&path, &origin, &issue, &path_attrs);
+                } else {
+                    fs::File::create(&path)?.write_all(content.as_bytes())?;
+                    self.journal.record(Action::Modify {
+                        path: path.clone(),
+                        old_content: origin.content.clone().unwrap_or_default(),
+                        new_content: content.clone(),
+                    })?;
+                    self.history.append(&self.change_records(&origin, &issue, &path_attrs))?;
+                    println!("{}", path);
+                }
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    fn issue_record(&self, issue: &Issue<String>) -> Result<IssueRecord, Error> {
+        self.find.issue_record(issue)
+    }
+}
@@ -1,7 +1,40 @@
 pub mod fs;
 pub mod mongo;
 
+use failure::Error;
+
 use todo::command::IssueCommand;
+use todo::exchange::IssueRecord;
+use todo::issue::Issue;
+
+pub trait Create: IssueCommand {
+    fn generates_id(&self) -> bool {
+        false
+    }
+
+    fn reserve_id(&mut self, _id: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn target_path(&self, _issue: &Issue<String>) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+}
+
+pub trait Find: IssueCommand {
+    fn find_issues(&mut self) -> Result<Vec<Issue<String>>, Error>;
+
+    fn issue_record(&self, issue: &Issue<String>) -> Result<IssueRecord, Error> {
+        Ok(IssueRecord::from_issue(issue, issue.content.clone()))
+    }
+}
+
+pub trait Update: IssueCommand {
+    fn update<F>(&mut self, f: F) -> Result<usize, Error>
+    where
+        F: FnMut(&mut Issue<String>) -> Result<(), Error>;
 
-pub trait Create: IssueCommand {}
-pub trait Find: IssueCommand {}
+    fn issue_record(&self, issue: &Issue<String>) -> Result<IssueRecord, Error> {
+        Ok(IssueRecord::from_issue(issue, issue.content.clone()))
+    }
+}
@@ -0,0 +1,75 @@
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use todo::attrs::Attrs;
+use todo::command::store::Update;
+use todo::command::Command;
+use todo::error::TodoError;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Tag<T>
+where
+    T: Update,
+{
+    pub update: Option<T>,
+    pub issue: Issue<String>,
+    pub add_tags: Vec<String>,
+    pub remove_tags: Vec<String>,
+}
+
+impl<T> Command for Tag<T>
+where
+    T: Update,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            let mut is_update_param = false;
+            if let Some(update) = self.update.as_mut() {
+                is_update_param = update.set_param(param, value.clone()).is_ok();
+            }
+            if !is_update_param {
+                self.issue.attrs.set_attr_value(param.to_lowercase().as_str(), value);
+            }
+        } else if value.starts_with('+') {
+            self.add_tags.extend(Attrs::split_values(&value[1..]));
+        } else if value.starts_with('-') {
+            self.remove_tags.extend(Attrs::split_values(&value[1..]));
+        } else if self.issue.get_id().is_none() {
+            let id_attr_key = self.issue.id_attr_key.clone();
+            self.issue.attrs.set_attr_value(id_attr_key.as_str(), value);
+        } else {
+            self.add_tags.extend(Attrs::split_values(&value));
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.issue.id_attr_key.as_str()
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut update = mem::replace(&mut self.update, None)
+            .expect("Update command not exist");
+
+        self.issue.required_id()?;
+        update.init_from(&self.issue)?;
+
+        let tags_attr_key = self.issue.tags_attr_key.as_str();
+        let (add_tags, remove_tags) = (&self.add_tags, &self.remove_tags);
+        update.update(|issue| {
+            let mut tags = issue.get_values(tags_attr_key);
+            tags.retain(|tag| !remove_tags.contains(tag));
+            for tag in add_tags {
+                if !tags.contains(tag) {
+                    tags.push(tag.clone());
+                }
+            }
+            issue.attrs.set_attr_value(tags_attr_key, Attrs::join_values(&tags));
+            Ok(())
+        })?;
+        self.update = Some(update);
+        Ok(())
+    }
+}
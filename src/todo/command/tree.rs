@@ -0,0 +1,60 @@
+use std::mem;
+use std::result::Result;
+use failure::Error;
+
+use todo::command::store::Find;
+use todo::command::Command;
+use todo::error::TodoError;
+use todo::hierarchy::Hierarchy;
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Tree<T>
+where
+    T: Find,
+{
+    pub find: Option<T>,
+    pub issue: Issue<String>,
+}
+
+impl<T> Command for Tree<T>
+where
+    T: Find,
+{
+    fn set_param(&mut self, param: &str, value: String) -> Result<(), TodoError> {
+        if !param.is_empty() {
+            let mut is_find_param = false;
+            if let Some(find) = self.find.as_mut() {
+                is_find_param = find.set_param(param, value.clone()).is_ok();
+            }
+            if !is_find_param {
+                self.issue.attrs.set_attr_value(param.to_lowercase().as_str(), value);
+            }
+        } else if let Some(find) = self.find.as_mut() {
+            let default_key = find.default_param_key().to_string();
+            find.set_param(&default_key, value)?;
+        }
+        Ok(())
+    }
+
+    fn default_param_key(&self) -> &str {
+        self.find
+            .as_ref()
+            .map(|find| find.default_param_key())
+            .expect("Find command not exist")
+    }
+
+    fn exec(&mut self) -> Result<(), Error> {
+        let mut find = mem::replace(&mut self.find, None)
+            .expect("Find command not exist");
+
+        find.init_from(&self.issue)?;
+        let issues = find.find_issues()?;
+        let hierarchy = Hierarchy::from_issues(&issues);
+        for node in hierarchy.tree() {
+            println!("{}{}", "  ".repeat(node.depth), hierarchy.issue_line(node.issue));
+        }
+        self.find = Some(find);
+        Ok(())
+    }
+}
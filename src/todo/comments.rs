@@ -0,0 +1,133 @@
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comment {
+    pub timestamp: String,
+    pub author: String,
+    pub text: String,
+}
+
+const ENTRY_PREFIX: &str = "### ";
+const ATTR_PREFIX: &str = "#[";
+const ESCAPED_ATTR_PREFIX: &str = " #[";
+
+// Attrs are only parsed at the line start, so an indent keeps comment text out of issue attrs
+fn escape_text(text: &str) -> String {
+    text.lines()
+        .map(|line| if line.starts_with(ATTR_PREFIX) { format!(" {}", line) } else { line.to_string() })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn section_pos(content: &str, section: &str) -> Option<usize> {
+    if section.is_empty() {
+        return None;
+    }
+    let mut pos = 0;
+    for line in content.split('\n') {
+        if line.trim_end() == section {
+            return Some(pos);
+        }
+        pos += line.len() + 1;
+    }
+    None
+}
+
+pub fn append_comment(content: &mut String, section: &str, comment: &Comment) {
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if section_pos(content, section).is_none() {
+        if !content.is_empty() {
+            content.push('\n');
+        }
+        content.push_str(section);
+        content.push('\n');
+    }
+    content.push_str(&format!(
+        "\n{}{} {}\n{}\n",
+        ENTRY_PREFIX,
+        comment.timestamp,
+        comment.author,
+        escape_text(comment.text.trim_end())
+    ));
+}
+
+pub fn split_comments<'a>(content: &'a str, section: &str) -> (&'a str, Vec<Comment>) {
+    let pos = match section_pos(content, section) {
+        Some(pos) => pos,
+        None => return (content, Vec::new()),
+    };
+
+    let mut comments: Vec<Comment> = Vec::new();
+    for line in content[pos..].lines().skip(1) {
+        if line.starts_with(ENTRY_PREFIX) {
+            let header = &line[ENTRY_PREFIX.len()..];
+            // The timestamp has a fixed `%Y-%m-%d %H:%M:%S` width
+            let (timestamp, author) = if header.len() > 19 && header.is_char_boundary(19) {
+                (&header[..19], header[19..].trim())
+            } else {
+                (header, "")
+            };
+            comments.push(Comment {
+                timestamp: timestamp.to_string(),
+                author: author.to_string(),
+                text: String::new(),
+            });
+        } else if let Some(comment) = comments.last_mut() {
+            let line = if line.starts_with(ESCAPED_ATTR_PREFIX) { &line[1..] } else { line };
+            comment.text.push_str(line);
+            comment.text.push('\n');
+        }
+    }
+    for comment in &mut comments {
+        comment.text = comment.text.trim().to_string();
+    }
+    (&content[..pos], comments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment(timestamp: &str, author: &str, text: &str) -> Comment {
+        Comment { timestamp: timestamp.to_string(), author: author.to_string(), text: text.to_string() }
+    }
+
+    #[test]
+    fn append_comment() {
+        let mut content = "#[id: 1]\ntext".to_string();
+        super::append_comment(&mut content, "## Comments", &comment("2018-06-04 10:00:00", "Tester", "first"));
+        super::append_comment(&mut content, "## Comments", &comment("2018-06-05 10:00:00", "Other", "second\nline\n"));
+        assert_eq!(
+            "#[id: 1]\ntext\n\n## Comments\n\n### 2018-06-04 10:00:00 Tester\nfirst\n\n### 2018-06-05 10:00:00 Other\nsecond\nline\n",
+            content
+        );
+
+        let mut content = String::new();
+        super::append_comment(&mut content, "## Comments", &comment("2018-06-04 10:00:00", "Tester", "first"));
+        assert_eq!("## Comments\n\n### 2018-06-04 10:00:00 Tester\nfirst\n", content);
+
+        let mut content = "#[id: 1]\n".to_string();
+        super::append_comment(&mut content, "## Comments", &comment("2018-06-04 10:00:00", "Tester", "#[closed: x]\nnote #[a: b]"));
+        assert_eq!("#[id: 1]\n\n## Comments\n\n### 2018-06-04 10:00:00 Tester\n #[closed: x]\nnote #[a: b]\n", content);
+        assert_eq!(
+            vec![comment("2018-06-04 10:00:00", "Tester", "#[closed: x]\nnote #[a: b]")],
+            super::split_comments(&content, "## Comments").1
+        );
+    }
+
+    #[test]
+    fn split_comments() {
+        let content = "text\n\n## Comments\n\n### 2018-06-04 10:00:00 Tester\nfirst\n\n### 2018-06-05 10:00:00 Other\nsecond\nline\n";
+        let (body, comments) = super::split_comments(content, "## Comments");
+        assert_eq!("text\n\n", body);
+        assert_eq!(
+            vec![
+                comment("2018-06-04 10:00:00", "Tester", "first"),
+                comment("2018-06-05 10:00:00", "Other", "second\nline"),
+            ],
+            comments
+        );
+
+        assert_eq!(("text\n", Vec::new()), super::split_comments("text\n", "## Comments"));
+    }
+}
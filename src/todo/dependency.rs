@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+use todo::issue::Issue;
+
+#[derive(Clone, Debug, Default)]
+pub struct Dependencies {
+    blockers: HashMap<String, Vec<String>>,
+    blocked: HashMap<String, Vec<String>>,
+    open: HashMap<String, bool>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChainLink {
+    pub depth: usize,
+    pub id: String,
+    pub is_cycle: bool,
+}
+
+impl Dependencies {
+    pub fn from_issues<'a, I>(issues: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Issue<String>>,
+    {
+        let mut deps = Dependencies::default();
+        for issue in issues {
+            if let Some(id) = issue.get_id() {
+                deps.open.insert(id.clone(), !issue.is_closed());
+                for blocker in issue.get_values(&issue.blocked_by_attr_key) {
+                    deps.add_link(&blocker, id);
+                }
+                for blocked in issue.get_values(&issue.blocks_attr_key) {
+                    deps.add_link(id, &blocked);
+                }
+            }
+        }
+        deps
+    }
+
+    pub fn add_link(&mut self, blocker: &str, blocked: &str) {
+        let blockers = self.blockers.entry(blocked.to_string()).or_insert_with(Vec::new);
+        if !blockers.iter().any(|id| id == blocker) {
+            blockers.push(blocker.to_string());
+        }
+        let blocked_ids = self.blocked.entry(blocker.to_string()).or_insert_with(Vec::new);
+        if !blocked_ids.iter().any(|id| id == blocked) {
+            blocked_ids.push(blocked.to_string());
+        }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.open.contains_key(id)
+    }
+
+    pub fn is_open(&self, id: &str) -> bool {
+        self.open.get(id).cloned().unwrap_or(false)
+    }
+
+    pub fn blockers(&self, id: &str) -> &[String] {
+        self.blockers.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn blocked(&self, id: &str) -> &[String] {
+        self.blocked.get(id).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    pub fn is_ready(&self, id: &str) -> bool {
+        !self.blockers(id).iter().any(|blocker| self.is_open(blocker))
+    }
+
+    pub fn is_blocked_by(&self, id: &str, blocker: &str) -> bool {
+        let mut stack = vec![id];
+        let mut visited: Vec<&str> = Vec::new();
+        while let Some(id) = stack.pop() {
+            for next in self.blockers(id) {
+                if next == blocker {
+                    return true;
+                }
+                if !visited.contains(&next.as_str()) {
+                    visited.push(next);
+                    stack.push(next);
+                }
+            }
+        }
+        false
+    }
+
+    pub fn blockers_chain(&self, id: &str) -> Vec<ChainLink> {
+        let mut chain = Vec::new();
+        self.collect_chain(id, 0, &mut vec![id.to_string()], &mut chain, &|id| self.blockers(id));
+        chain
+    }
+
+    pub fn blocked_chain(&self, id: &str) -> Vec<ChainLink> {
+        let mut chain = Vec::new();
+        self.collect_chain(id, 0, &mut vec![id.to_string()], &mut chain, &|id| self.blocked(id));
+        chain
+    }
+
+    fn collect_chain<'a, F>(&'a self, id: &str, depth: usize, path: &mut Vec<String>, chain: &mut Vec<ChainLink>, next: &F)
+    where
+        F: Fn(&str) -> &'a [String],
+    {
+        for next_id in next(id) {
+            let is_cycle = path.contains(next_id);
+            chain.push(ChainLink { depth, id: next_id.clone(), is_cycle });
+            if !is_cycle {
+                path.push(next_id.clone());
+                self.collect_chain(next_id, depth + 1, path, chain, next);
+                path.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use todo::issue::fixture::issue_with as issue;
+
+    #[test]
+    fn dependencies() {
+        let issues = vec![
+            issue("1", &[("blocked_by", "2, 3")]),
+            issue("2", &[("closed", "2018-06-04")]),
+            issue("3", &[("blocks", "4")]),
+            issue("4", &[]),
+            issue("5", &[("blocked_by", "9")]),
+        ];
+        let deps = Dependencies::from_issues(&issues);
+
+        assert_eq!(&["2".to_string(), "3".to_string()], deps.blockers("1"));
+        assert_eq!(&["1".to_string(), "4".to_string()], deps.blocked("3"));
+        assert!(!deps.is_ready("1"));
+        assert!(deps.is_ready("2"));
+        assert!(deps.is_ready("3"));
+        assert!(!deps.is_ready("4"));
+        assert!(deps.is_ready("5"));
+        assert!(!deps.contains("9"));
+
+        assert!(deps.is_blocked_by("1", "3"));
+        assert!(!deps.is_blocked_by("3", "1"));
+        assert!(!deps.is_blocked_by("4", "1"));
+    }
+
+    #[test]
+    fn chain() {
+        let mut deps = Dependencies::from_issues(&vec![
+            issue("1", &[("blocked_by", "2")]),
+            issue("2", &[("blocked_by", "3")]),
+            issue("3", &[]),
+        ]);
+        let link = |depth, id: &str, is_cycle| ChainLink { depth, id: id.to_string(), is_cycle };
+
+        assert_eq!(vec![link(0, "2", false), link(1, "3", false)], deps.blockers_chain("1"));
+        assert_eq!(vec![link(0, "1", false)], deps.blocked_chain("2"));
+
+        deps.add_link("1", "3");
+        assert!(deps.is_blocked_by("3", "3"));
+        assert_eq!(
+            vec![link(0, "2", false), link(1, "3", false), link(2, "1", true)],
+            deps.blockers_chain("1")
+        );
+    }
+}
@@ -14,6 +14,108 @@ pub enum TodoError {
     #[fail(display = "alias `{}` already exists for key `{}`", alias, key)]
     AliasAlreadyExists { alias: String, key: String },
 
+    #[fail(display = "`{}` is not specified", param)]
+    ParamNotSpecified { param: String },
+
     #[fail(display = "file is not specified")]
     FileNotSpecified,
+
+    #[fail(display = "unsupported generator type `{}`", name)]
+    UnsupportedGenerator { name: String },
+
+    #[fail(display = "unsupported attrs style `{}`", style)]
+    UnsupportedAttrsStyle { style: String },
+
+    #[fail(display = "unsupported timezone `{}`, only fixed offsets like `+02:00` or `utc` are supported", value)]
+    UnsupportedTimezone { value: String },
+
+    #[fail(display = "can't create the next recurring issue of `{}` without id generator", id)]
+    IdGeneratorRequired { id: String },
+
+    #[fail(display = "unsupported exchange format `{}`", format)]
+    UnsupportedFormat { format: String },
+
+    #[fail(display = "invalid regular expression `{}`", value)]
+    InvalidRegex { value: String },
+
+    #[fail(display = "invalid expression `{}`", value)]
+    InvalidExpr { value: String },
+
+    #[fail(display = "invalid date `{}`", value)]
+    InvalidDate { value: String },
+
+    #[fail(display = "invalid recurrence `{}`", value)]
+    InvalidRecurrence { value: String },
+
+    #[fail(display = "invalid progress `{}`", value)]
+    InvalidProgress { value: String },
+
+    #[fail(display = "invalid duration `{}`", value)]
+    InvalidDuration { value: String },
+
+    #[fail(display = "invalid path value `{}`", value)]
+    InvalidPathValue { value: String },
+
+    #[fail(display = "checklist item `{}` is not found", number)]
+    ChecklistItemNotFound { number: usize },
+
+    #[fail(display = "template `{}` is not found", name)]
+    TemplateNotFound { name: String },
+
+    #[fail(display = "issue `{}` is not found", id)]
+    IssueNotFound { id: String },
+
+    #[fail(display = "linking `{}` to `{}` makes a dependency cycle", from, to)]
+    DependencyCycle { from: String, to: String },
+
+    #[fail(display = "file `{}` already exists", path)]
+    FileAlreadyExists { path: String },
+
+    #[fail(display = "editor `{}` exited with error", editor)]
+    EditorFailed { editor: String },
+
+    #[fail(display = "aborted: empty issue content")]
+    EditAborted,
+
+    #[fail(display = "can't undo: `{}` was changed after the last operation", path)]
+    UnsafeUndo { path: String },
+}
+
+impl TodoError {
+    pub const EXIT_FAILURE: i32 = 1;
+    pub const EXIT_USAGE: i32 = 2;
+    pub const EXIT_NOT_FOUND: i32 = 3;
+    pub const EXIT_CONFLICT: i32 = 4;
+    pub const EXIT_CONFIG: i32 = 5;
+
+    pub fn exit_code(&self) -> i32 {
+        match *self {
+            TodoError::UnknownCommand { .. }
+            | TodoError::UnknownCommandParam { .. }
+            | TodoError::ParamNotSpecified { .. }
+            | TodoError::InvalidRegex { .. }
+            | TodoError::InvalidExpr { .. }
+            | TodoError::InvalidDate { .. }
+            | TodoError::InvalidRecurrence { .. }
+            | TodoError::InvalidProgress { .. }
+            | TodoError::InvalidDuration { .. }
+            | TodoError::InvalidPathValue { .. }
+            | TodoError::UnsupportedFormat { .. } => Self::EXIT_USAGE,
+            TodoError::KeyNotFound { .. }
+            | TodoError::IssueNotFound { .. }
+            | TodoError::TemplateNotFound { .. }
+            | TodoError::ChecklistItemNotFound { .. } => Self::EXIT_NOT_FOUND,
+            TodoError::DependencyCycle { .. }
+            | TodoError::FileAlreadyExists { .. }
+            | TodoError::UnsafeUndo { .. } => Self::EXIT_CONFLICT,
+            TodoError::AliasAlreadyExists { .. }
+            | TodoError::FileNotSpecified
+            | TodoError::UnsupportedGenerator { .. }
+            | TodoError::UnsupportedAttrsStyle { .. }
+            | TodoError::UnsupportedTimezone { .. }
+            | TodoError::IdGeneratorRequired { .. } => Self::EXIT_CONFIG,
+            TodoError::EditorFailed { .. }
+            | TodoError::EditAborted => Self::EXIT_FAILURE,
+        }
+    }
 }
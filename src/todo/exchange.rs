@@ -0,0 +1,198 @@
+use std::fmt;
+
+use failure::Error;
+use serde::de::{MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserializer, Serializer};
+use serde_json;
+
+use date::Calendar;
+use todo::error::TodoError;
+use todo::issue::Issue;
+use todo::{taskwarrior, todotxt};
+
+pub const PRIORITY_KEY: &str = "priority";
+pub const SCOPE_KEY: &str = "scope";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExchangeFormat {
+    Json,
+    TodoTxt,
+    Taskwarrior,
+}
+
+impl ExchangeFormat {
+    pub fn by_key(key: &str) -> Option<Self> {
+        Some(match key {
+            key if ExchangeFormat::Json.key() == key => ExchangeFormat::Json,
+            key if ExchangeFormat::TodoTxt.key() == key => ExchangeFormat::TodoTxt,
+            key if ExchangeFormat::Taskwarrior.key() == key => ExchangeFormat::Taskwarrior,
+            _ => return None,
+        })
+    }
+
+    pub fn parse(key: &str) -> Result<Self, TodoError> {
+        if key.is_empty() {
+            Ok(ExchangeFormat::Json)
+        } else {
+            ExchangeFormat::by_key(key).ok_or_else(|| TodoError::UnsupportedFormat { format: key.to_string() })
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match *self {
+            ExchangeFormat::Json => "json",
+            ExchangeFormat::TodoTxt => "todotxt",
+            ExchangeFormat::Taskwarrior => "taskwarrior",
+        }
+    }
+
+    pub fn write_records(
+        &self,
+        records: &[IssueRecord],
+        issue: &Issue<String>,
+        section: &str,
+        calendar: &Calendar,
+    ) -> Result<String, Error> {
+        Ok(match *self {
+            ExchangeFormat::Json => serde_json::to_string_pretty(records)?,
+            ExchangeFormat::TodoTxt => records.iter()
+                .map(|record| todotxt::encode(record, issue))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ExchangeFormat::Taskwarrior => taskwarrior::encode(records, issue, section, calendar)?,
+        })
+    }
+
+    pub fn read_records(
+        &self,
+        text: &str,
+        issue: &Issue<String>,
+        section: &str,
+        calendar: &Calendar,
+    ) -> Result<Vec<IssueRecord>, Error> {
+        Ok(match *self {
+            ExchangeFormat::Json => serde_json::from_str(text)?,
+            ExchangeFormat::TodoTxt => text.lines()
+                .filter_map(|line| todotxt::decode(line, issue, calendar))
+                .collect(),
+            ExchangeFormat::Taskwarrior => taskwarrior::decode(text, issue, section, calendar)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct IssueRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, with = "ordered_attrs")]
+    pub attrs: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl IssueRecord {
+    pub fn from_issue(issue: &Issue<String>, body: Option<String>) -> Self {
+        let attrs = issue.attrs.keys
+            .iter()
+            .filter(|key| ***key != issue.id_attr_key)
+            .map(|key| (key.to_string(), issue.attrs.attr_value_as_str(key).to_string()))
+            .filter(|&(_, ref value)| !value.is_empty())
+            .collect();
+
+        IssueRecord {
+            id: issue.get_id().filter(|id| !id.is_empty()).cloned(),
+            attrs,
+            body,
+        }
+    }
+
+    pub fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs.iter()
+            .find(|&&(ref attr_key, _)| attr_key == key)
+            .map(|&(_, ref value)| value.as_str())
+    }
+
+    pub fn set_attr(&mut self, key: &str, value: &str) {
+        match self.attrs.iter_mut().find(|&&mut (ref attr_key, _)| attr_key == key) {
+            Some(attr) => attr.1 = value.to_string(),
+            None => self.attrs.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn to_issue(&self, template: &Issue<String>) -> Issue<String> {
+        let mut issue = template.clone();
+        issue.attrs.clear_values();
+        if let Some(ref id) = self.id {
+            let id_attr_key = issue.id_attr_key.clone();
+            issue.attrs.set_attr_value(id_attr_key.as_str(), id.clone());
+        }
+        for &(ref key, ref value) in &self.attrs {
+            issue.attrs.set_attr_value(key.as_str(), value.clone());
+        }
+        issue.content = self.body.clone();
+        issue
+    }
+}
+
+mod ordered_attrs {
+    use super::*;
+
+    pub fn serialize<S>(attrs: &[(String, String)], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(attrs.len()))?;
+        for &(ref key, ref value) in attrs {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<(String, String)>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct AttrsVisitor;
+
+        impl<'de> Visitor<'de> for AttrsVisitor {
+            type Value = Vec<(String, String)>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of attr values")
+            }
+
+            fn visit_map<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+            where
+                M: MapAccess<'de>,
+            {
+                let mut attrs = Vec::new();
+                while let Some(entry) = access.next_entry()? {
+                    attrs.push(entry);
+                }
+                Ok(attrs)
+            }
+        }
+
+        deserializer.deserialize_map(AttrsVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn keep_attrs_order() {
+        let record = IssueRecord {
+            id: Some("12".to_string()),
+            attrs: vec![("name".to_string(), "task".to_string()), ("due".to_string(), "2024-01-02".to_string())],
+            body: None,
+        };
+
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(r#"{"id":"12","attrs":{"name":"task","due":"2024-01-02"}}"#, json);
+        assert_eq!(record, serde_json::from_str(&json).unwrap());
+    }
+}
@@ -0,0 +1,144 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use todo::issue::Issue;
+
+#[derive(Clone, Debug)]
+pub struct Hierarchy<'a> {
+    issues: Vec<&'a Issue<String>>,
+    children: HashMap<usize, Vec<usize>>,
+    roots: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TreeNode<'a> {
+    pub depth: usize,
+    pub issue: &'a Issue<String>,
+}
+
+impl<'a> Hierarchy<'a> {
+    pub fn from_issues<I>(issues: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Issue<String>>,
+    {
+        let mut issues: Vec<&Issue<String>> = issues.into_iter().collect();
+        issues.sort_by(|a, b| Self::cmp_ids(a.get_id(), b.get_id()));
+
+        let index: HashMap<&str, usize> = issues.iter()
+            .enumerate()
+            .filter_map(|(i, issue)| issue.get_id().map(|id| (id.as_str(), i)))
+            .collect();
+
+        let mut children = HashMap::new();
+        let mut roots = Vec::new();
+        for (i, issue) in issues.iter().enumerate() {
+            match issue.get_parent().and_then(|parent| index.get(parent.as_str())) {
+                Some(&parent) if parent != i => children.entry(parent).or_insert_with(Vec::new).push(i),
+                _ => roots.push(i),
+            }
+        }
+
+        Hierarchy { issues, children, roots }
+    }
+
+    fn cmp_ids(a: Option<&String>, b: Option<&String>) -> Ordering {
+        let number = |id: Option<&String>| id.and_then(|id| id.parse::<u64>().ok());
+        number(a).cmp(&number(b)).then_with(|| a.cmp(&b))
+    }
+
+    fn index_of(&self, id: &str) -> Option<usize> {
+        self.issues.iter().position(|issue| issue.get_id().map(String::as_str) == Some(id))
+    }
+
+    pub fn children(&self, id: &str) -> Vec<&'a Issue<String>> {
+        self.index_of(id)
+            .and_then(|i| self.children.get(&i))
+            .map(|children| children.iter().map(|&i| self.issues[i]).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn progress(&self, id: &str) -> Option<(usize, usize)> {
+        let children = self.children(id);
+        if children.is_empty() {
+            None
+        } else {
+            let closed = children.iter().filter(|issue| issue.is_closed()).count();
+            Some((closed, children.len()))
+        }
+    }
+
+    pub fn issue_line(&self, issue: &Issue<String>) -> String {
+        let id = issue.get_id().map(String::as_str).unwrap_or("-");
+        let mut line = id.to_string();
+        if !issue.get_title().is_empty() {
+            line = line + " " + issue.get_title();
+        }
+        if issue.is_closed() {
+            line += " [closed]";
+        }
+        if let Some((closed, total)) = self.progress(id) {
+            line += &format!(" [{}/{}]", closed, total);
+        }
+        line
+    }
+
+    pub fn tree(&self) -> Vec<TreeNode<'a>> {
+        let mut visited = vec![false; self.issues.len()];
+        let mut nodes = Vec::new();
+        for &root in &self.roots {
+            self.collect_tree(root, 0, &mut visited, &mut nodes);
+        }
+        // Issues whose parents form a cycle never reach a root
+        for i in 0..self.issues.len() {
+            if !visited[i] {
+                self.collect_tree(i, 0, &mut visited, &mut nodes);
+            }
+        }
+        nodes
+    }
+
+    fn collect_tree(&self, i: usize, depth: usize, visited: &mut Vec<bool>, nodes: &mut Vec<TreeNode<'a>>) {
+        if visited[i] {
+            return;
+        }
+        visited[i] = true;
+        nodes.push(TreeNode { depth, issue: self.issues[i] });
+        if let Some(children) = self.children.get(&i) {
+            for &child in children {
+                self.collect_tree(child, depth + 1, visited, nodes);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use todo::issue::fixture::issue_with as issue;
+
+    #[test]
+    fn tree() {
+        let issues = vec![
+            issue("10", &[("parent", "2")]),
+            issue("2", &[]),
+            issue("3", &[("parent", "2"), ("closed", "2018-06-04")]),
+            issue("4", &[("parent", "3")]),
+            issue("5", &[("parent", "99")]),
+            issue("6", &[("parent", "7")]),
+            issue("7", &[("parent", "6")]),
+        ];
+        let hierarchy = Hierarchy::from_issues(&issues);
+        let tree: Vec<(usize, &str)> = hierarchy.tree()
+            .iter()
+            .map(|node| (node.depth, node.issue.get_id().unwrap().as_str()))
+            .collect();
+
+        assert_eq!(
+            vec![(0, "2"), (1, "3"), (2, "4"), (1, "10"), (0, "5"), (0, "6"), (1, "7")],
+            tree
+        );
+        assert_eq!(Some((1, 2)), hierarchy.progress("2"));
+        assert_eq!(Some((0, 1)), hierarchy.progress("3"));
+        assert_eq!(None, hierarchy.progress("4"));
+    }
+}
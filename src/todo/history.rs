@@ -0,0 +1,109 @@
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+
+use failure::Error;
+
+use todo::command::store::fs::{Action, Journal};
+
+const FIELDS_SEPARATOR: char = '\t';
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangeRecord {
+    pub timestamp: String,
+    pub user: String,
+    pub id: String,
+    pub key: String,
+    pub old: String,
+    pub new: String,
+}
+
+impl ChangeRecord {
+    pub fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split(FIELDS_SEPARATOR).collect();
+        if fields.len() != 6 {
+            return None;
+        }
+        Some(ChangeRecord {
+            timestamp: fields[0].to_string(),
+            user: fields[1].to_string(),
+            id: fields[2].to_string(),
+            key: fields[3].to_string(),
+            old: fields[4].to_string(),
+            new: fields[5].to_string(),
+        })
+    }
+
+    pub fn to_line(&self) -> String {
+        [&self.timestamp, &self.user, &self.id, &self.key, &self.old, &self.new]
+            .iter()
+            .map(|field| field.replace(|ch| ch == FIELDS_SEPARATOR || ch == '\n', " "))
+            .collect::<Vec<_>>()
+            .join(&FIELDS_SEPARATOR.to_string())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct HistoryLog {
+    pub file: Option<String>,
+    pub journal: Journal,
+}
+
+impl HistoryLog {
+    fn read_text(&self) -> Result<String, Error> {
+        let mut text = String::new();
+        if let Some(ref path) = self.file {
+            if let Ok(mut file) = File::open(path) {
+                file.read_to_string(&mut text)?;
+            }
+        }
+        Ok(text)
+    }
+
+    pub fn read_records(&self, id: &str) -> Result<Vec<ChangeRecord>, Error> {
+        Ok(self.read_text()?.lines()
+            .filter_map(ChangeRecord::parse)
+            .filter(|record| record.id == id)
+            .collect())
+    }
+
+    pub fn append(&self, records: &[ChangeRecord]) -> Result<(), Error> {
+        if let Some(ref path) = self.file {
+            if !records.is_empty() {
+                let mut text = String::new();
+                for record in records {
+                    text += &record.to_line();
+                    text.push('\n');
+                }
+                let old_content = self.read_text()?;
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)?
+                    .write_all(text.as_bytes())?;
+                let new_content = old_content.clone() + &text;
+                self.journal.record(Action::Modify { path: path.clone(), old_content, new_content })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_record() {
+        let line = "2018-06-04 10:00:00\tTester\t12\tpriority\tA\tB";
+        let record = ChangeRecord::parse(line).unwrap();
+        assert_eq!("priority", record.key);
+        assert_eq!("A", record.old);
+        assert_eq!("B", record.new);
+        assert_eq!(line, record.to_line());
+
+        let record = ChangeRecord { new: "a\tb".to_string(), ..record };
+        assert_eq!("2018-06-04 10:00:00\tTester\t12\tpriority\tA\ta b", record.to_line());
+
+        assert_eq!(None, ChangeRecord::parse("2018-06-04 10:00:00\tTester"));
+    }
+}
@@ -1,4 +1,7 @@
+use date::{self, Calendar};
 use todo::attrs::Attrs;
+use todo::checklist;
+use todo::error::TodoError;
 
 pub trait Content {
     fn content(&self) -> String;
@@ -13,6 +16,14 @@ impl Content for String {
 #[derive(Clone, Debug, Default)]
 pub struct Issue<T: Content> {
     pub id_attr_key: String,
+    pub tags_attr_key: String,
+    pub due_attr_key: String,
+    pub closed_attr_key: String,
+    pub recur_attr_key: String,
+    pub blocks_attr_key: String,
+    pub blocked_by_attr_key: String,
+    pub parent_attr_key: String,
+    pub progress_attr_key: String,
     pub attrs: Attrs,
     pub content: Option<T>,
 }
@@ -21,4 +32,157 @@ impl<T: Content> Issue<T> {
     pub fn get_id(&self) -> Option<&String> {
         self.attrs.attr_value(&self.id_attr_key)
     }
+
+    pub fn required_id(&self) -> Result<&String, TodoError> {
+        self.get_id().ok_or_else(|| TodoError::ParamNotSpecified { param: self.id_attr_key.clone() })
+    }
+
+    pub fn normalize_attr_value(&self, alias: &str, value: String, calendar: &Calendar) -> Result<String, TodoError> {
+        let is_due = self.attrs
+            .key_by_alias(alias)
+            .map(|key| *key == self.due_attr_key)
+            .unwrap_or(false);
+
+        if is_due && !value.is_empty() {
+            calendar
+                .parse_date(&value)
+                .map(date::format_date)
+                .ok_or(TodoError::InvalidDate { value })
+        } else {
+            Ok(value)
+        }
+    }
+
+    pub fn get_values(&self, key: &str) -> Vec<String> {
+        self.attrs.attr_values(key)
+    }
+
+    pub fn get_title(&self) -> &str {
+        self.attrs.attr_value_as_str(self.attrs.default_key.as_str())
+    }
+
+    pub fn get_parent(&self) -> Option<&String> {
+        self.attrs.attr_value(&self.parent_attr_key)
+            .filter(|parent| !parent.is_empty())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        !self.attrs.attr_value_as_str(&self.closed_attr_key).is_empty()
+    }
+
+    pub fn get_progress(&self) -> Option<(usize, usize)> {
+        self.content
+            .as_ref()
+            .and_then(|content| checklist::checklist_progress(&content.content()))
+    }
+
+    pub fn get_tags(&self) -> Vec<String> {
+        let mut tags = self.get_values(&self.tags_attr_key);
+        if let Some(ref content) = self.content {
+            for tag in body_tags(&content.content()) {
+                if !tags.contains(&tag) {
+                    tags.push(tag);
+                }
+            }
+        }
+        tags
+    }
+
+    pub fn to_string_issue(&self) -> Issue<String> {
+        Issue {
+            id_attr_key: self.id_attr_key.clone(),
+            tags_attr_key: self.tags_attr_key.clone(),
+            due_attr_key: self.due_attr_key.clone(),
+            closed_attr_key: self.closed_attr_key.clone(),
+            recur_attr_key: self.recur_attr_key.clone(),
+            blocks_attr_key: self.blocks_attr_key.clone(),
+            blocked_by_attr_key: self.blocked_by_attr_key.clone(),
+            parent_attr_key: self.parent_attr_key.clone(),
+            progress_attr_key: self.progress_attr_key.clone(),
+            attrs: self.attrs.clone(),
+            content: self.content.as_ref().map(Content::content),
+        }
+    }
+}
+
+pub fn body_tags(text: &str) -> Vec<String> {
+    let is_tag_char = |ch: char| ch.is_alphanumeric() || ch == '_' || ch == '-' || ch == '/';
+    let mut tags: Vec<String> = Vec::new();
+    let mut prev = ' ';
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, ch)) = chars.next() {
+        if ch == '#' && !prev.is_alphanumeric() && !['#', '&', '/'].contains(&prev) {
+            // Issue numbers like `#12` and colours like `#0f0f0f` are not tags
+            if let Some(&(_, next)) = chars.peek() {
+                if next.is_alphabetic() {
+                    let tag: String = text[(i + 1)..].chars().take_while(|&ch| is_tag_char(ch)).collect();
+                    let tag = tag.trim_end_matches(|ch| ch == '-' || ch == '/').to_string();
+                    if !tags.contains(&tag) {
+                        tags.push(tag);
+                    }
+                }
+            }
+        }
+        prev = ch;
+    }
+    tags
+}
+
+#[cfg(test)]
+pub mod fixture {
+    use std::rc::Rc;
+
+    use super::Issue;
+
+    pub fn issue() -> Issue<String> {
+        let mut issue = Issue::<String>::default();
+        issue.id_attr_key = "id".to_string();
+        issue.tags_attr_key = "tags".to_string();
+        issue.due_attr_key = "due".to_string();
+        issue.closed_attr_key = "closed".to_string();
+        issue.blocks_attr_key = "blocks".to_string();
+        issue.blocked_by_attr_key = "blocked_by".to_string();
+        issue.parent_attr_key = "parent".to_string();
+        issue.attrs.add_multi_value_key("blocks");
+        issue.attrs.add_multi_value_key("blocked_by");
+        issue.attrs.default_key = Rc::new("name".to_string());
+        issue
+    }
+
+    pub fn issue_with(id: &str, attrs: &[(&str, &str)]) -> Issue<String> {
+        let mut issue = issue();
+        issue.attrs.set_attr_value("id", id);
+        for &(key, value) in attrs {
+            issue.attrs.set_attr_value(key, value);
+        }
+        issue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn body_tags() {
+        assert!(super::body_tags("").is_empty());
+        assert!(super::body_tags("# Title\n#[tags: a]\n## Sub\npage#anchor #\n").is_empty());
+        assert!(super::body_tags("fixed in #12, color: #0f0f0f; #_private").is_empty());
+        assert_eq!(
+            vec!["bug", "ui/menu", "x_1"],
+            super::body_tags("#bug in the #ui/menu,\n(#x_1) #bug #- text")
+        );
+    }
+
+    #[test]
+    fn get_tags() {
+        let mut issue = Issue::<String>::default();
+        issue.tags_attr_key = "tags".to_string();
+        issue.attrs.add_multi_value_key("tags");
+        issue.attrs.set_attr_value("tags", "a, b");
+        issue.content = Some("#[tags: a, b]\ntext #c #a\n".to_string());
+
+        assert_eq!(vec!["a", "b", "c"], issue.get_tags());
+    }
 }
@@ -1,5 +1,14 @@
 pub mod attrs;
+pub mod checklist;
 pub mod command;
+pub mod comments;
+pub mod dependency;
 pub mod error;
+pub mod exchange;
+pub mod hierarchy;
+pub mod history;
 pub mod issue;
 pub mod lang;
+pub mod taskwarrior;
+pub mod timelog;
+pub mod todotxt;
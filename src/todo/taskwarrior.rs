@@ -0,0 +1,205 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use failure::Error;
+use serde_json;
+
+use date::{self, Calendar, DATETIME_FORMAT, DATE_FORMAT};
+use todo::attrs::Attrs;
+use todo::comments::{self, Comment};
+use todo::exchange::{IssueRecord, PRIORITY_KEY, SCOPE_KEY};
+use todo::issue::Issue;
+
+pub const UUID_KEY: &str = "uuid";
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const COMPLETED_STATUS: &str = "completed";
+const DELETED_STATUS: &str = "deleted";
+const PENDING_STATUS: &str = "pending";
+const ANNOTATION_AUTHOR: &str = "taskwarrior";
+const PRIORITIES: [&str; 3] = ["H", "M", "L"];
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub entry: String,
+    pub description: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uuid: Option<String>,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<Annotation>,
+}
+
+fn encode_date(value: &str, calendar: &Calendar) -> Option<String> {
+    NaiveDateTime::parse_from_str(value, DATETIME_FORMAT)
+        .ok()
+        .or_else(|| NaiveDate::parse_from_str(value, DATE_FORMAT).ok().and_then(|date| date.and_hms_opt(0, 0, 0)))
+        .map(|datetime| calendar.to_utc(datetime).format(DATE_TIME_FORMAT).to_string())
+}
+
+fn decode_date(value: &str, format: &str, calendar: &Calendar) -> Option<String> {
+    NaiveDateTime::parse_from_str(value, DATE_TIME_FORMAT)
+        .ok()
+        .map(|datetime| calendar.from_utc(datetime).format(format).to_string())
+}
+
+// Taskwarrior accepts only H, M and L priorities, other filename priorities have no counterpart
+fn encode_priority(value: Option<&str>) -> Option<String> {
+    value.filter(|value| PRIORITIES.contains(value)).map(str::to_string)
+}
+
+fn non_empty(value: Option<&str>) -> Option<String> {
+    value.filter(|value| !value.is_empty()).map(str::to_string)
+}
+
+pub fn encode_task(record: &IssueRecord, issue: &Issue<String>, section: &str, calendar: &Calendar) -> Task {
+    let closed = record.attr(&issue.closed_attr_key).unwrap_or("");
+    let (_, comments) = comments::split_comments(record.body.as_ref().map(String::as_str).unwrap_or(""), section);
+
+    Task {
+        uuid: non_empty(record.attr(UUID_KEY)),
+        description: record.attr(&issue.attrs.default_key).unwrap_or("").to_string(),
+        status: Some(if closed.is_empty() { PENDING_STATUS } else { COMPLETED_STATUS }.to_string()),
+        project: non_empty(record.attr(SCOPE_KEY)),
+        priority: encode_priority(record.attr(PRIORITY_KEY)),
+        tags: Attrs::split_values(record.attr(&issue.tags_attr_key).unwrap_or("")),
+        due: record.attr(&issue.due_attr_key).and_then(|due| encode_date(due, calendar)),
+        end: encode_date(closed, calendar),
+        annotations: comments.into_iter()
+            .map(|comment| Annotation {
+                entry: encode_date(&comment.timestamp, calendar).unwrap_or(comment.timestamp),
+                description: comment.text,
+            })
+            .collect(),
+    }
+}
+
+pub fn decode_task(task: &Task, issue: &Issue<String>, section: &str, calendar: &Calendar) -> Option<IssueRecord> {
+    let status = task.status.as_ref().map(String::as_str).unwrap_or(PENDING_STATUS);
+    if status == DELETED_STATUS {
+        return None;
+    }
+
+    let mut record = IssueRecord::default();
+    record.set_attr(&issue.attrs.default_key, &task.description);
+    if let Some(ref uuid) = task.uuid {
+        record.set_attr(UUID_KEY, uuid);
+    }
+    if let Some(ref project) = task.project {
+        record.set_attr(SCOPE_KEY, project);
+    }
+    if let Some(ref priority) = task.priority {
+        record.set_attr(PRIORITY_KEY, priority);
+    }
+    if !task.tags.is_empty() {
+        record.set_attr(&issue.tags_attr_key, &Attrs::join_values(&task.tags));
+    }
+    if let Some(due) = task.due.as_ref().and_then(|due| decode_date(due, DATE_FORMAT, calendar)) {
+        record.set_attr(&issue.due_attr_key, &due);
+    }
+    if status == COMPLETED_STATUS {
+        let end = task.end.as_ref().and_then(|end| decode_date(end, DATE_FORMAT, calendar));
+        record.set_attr(&issue.closed_attr_key, &end.unwrap_or_else(|| date::format_date(calendar.today())));
+    }
+
+    let mut body = String::new();
+    for annotation in &task.annotations {
+        comments::append_comment(&mut body, section, &Comment {
+            timestamp: decode_date(&annotation.entry, DATETIME_FORMAT, calendar).unwrap_or_else(|| annotation.entry.clone()),
+            author: ANNOTATION_AUTHOR.to_string(),
+            text: annotation.description.clone(),
+        });
+    }
+    if !body.is_empty() {
+        record.body = Some(body);
+    }
+    Some(record)
+}
+
+pub fn encode(records: &[IssueRecord], issue: &Issue<String>, section: &str, calendar: &Calendar) -> Result<String, Error> {
+    let tasks: Vec<Task> = records.iter()
+        .map(|record| encode_task(record, issue, section, calendar))
+        .collect();
+    Ok(serde_json::to_string_pretty(&tasks)?)
+}
+
+pub fn decode(text: &str, issue: &Issue<String>, section: &str, calendar: &Calendar) -> Result<Vec<IssueRecord>, Error> {
+    let tasks: Vec<Task> = serde_json::from_str(text)?;
+    Ok(tasks.iter()
+        .filter_map(|task| decode_task(task, issue, section, calendar))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use todo::issue::fixture::issue;
+
+    #[test]
+    fn round_trip_task() {
+        let text = r#"[{
+            "id": 3,
+            "uuid": "5f1c",
+            "description": "Fix login",
+            "status": "completed",
+            "project": "web",
+            "priority": "H",
+            "tags": ["bug", "auth"],
+            "due": "20240105T000000Z",
+            "end": "20240104T101500Z",
+            "annotations": [{"entry": "20240103T093000Z", "description": "Reproduced"}]
+        }, {
+            "description": "Old",
+            "status": "deleted"
+        }, {
+            "description": "Done",
+            "status": "completed"
+        }]"#;
+
+        let calendar = Calendar { offset: date::parse_offset("+02:00") };
+        let records = decode(text, &issue(), "## Comments", &calendar).unwrap();
+        assert_eq!(2, records.len());
+        assert_eq!(Some(date::format_date(calendar.today()).as_str()), records[1].attr("closed"));
+        let record = &records[0];
+        assert_eq!(Some("Fix login"), record.attr("name"));
+        assert_eq!(Some("5f1c"), record.attr(UUID_KEY));
+        assert_eq!(Some("web"), record.attr(SCOPE_KEY));
+        assert_eq!(Some("bug, auth"), record.attr("tags"));
+        assert_eq!(Some("2024-01-05"), record.attr("due"));
+        assert_eq!(Some("2024-01-04"), record.attr("closed"));
+        assert_eq!(Some("H"), record.attr(PRIORITY_KEY));
+        assert_eq!(
+            Some("## Comments\n\n### 2024-01-03 11:30:00 taskwarrior\nReproduced\n"),
+            record.body.as_ref().map(String::as_str)
+        );
+
+        let task = encode_task(record, &issue(), "## Comments", &calendar);
+        assert_eq!(Some("completed".to_string()), task.status);
+        assert_eq!(Some("H".to_string()), task.priority);
+        assert_eq!(Some("20240104T220000Z".to_string()), task.due);
+        assert_eq!(Some("20240103T220000Z".to_string()), task.end);
+        assert_eq!(vec!["bug".to_string(), "auth".to_string()], task.tags);
+        assert_eq!(
+            vec![Annotation { entry: "20240103T093000Z".to_string(), description: "Reproduced".to_string() }],
+            task.annotations
+        );
+
+        let mut record = record.clone();
+        record.set_attr(PRIORITY_KEY, "O");
+        assert_eq!(None, encode_task(&record, &issue(), "## Comments", &calendar).priority);
+    }
+}
@@ -0,0 +1,192 @@
+use std::fs;
+use std::io::{Read, Write};
+
+use chrono::NaiveDateTime;
+use failure::Error;
+
+use date::DATETIME_FORMAT;
+use todo::command::store::fs::{Action, Journal};
+
+const FIELDS_SEPARATOR: char = '\t';
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeEntry {
+    pub id: String,
+    pub start: NaiveDateTime,
+    pub minutes: Option<i64>,
+}
+
+impl TimeEntry {
+    pub fn parse(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split(FIELDS_SEPARATOR).collect();
+        if fields.len() != 3 || fields[0].is_empty() {
+            return None;
+        }
+        Some(TimeEntry {
+            id: fields[0].to_string(),
+            start: NaiveDateTime::parse_from_str(fields[1], DATETIME_FORMAT).ok()?,
+            minutes: if fields[2].is_empty() { None } else { Some(fields[2].parse().ok()?) },
+        })
+    }
+
+    pub fn to_line(&self) -> String {
+        format!(
+            "{}{sep}{}{sep}{}",
+            self.id,
+            self.start.format(DATETIME_FORMAT),
+            self.minutes.map(|minutes| minutes.to_string()).unwrap_or_default(),
+            sep = FIELDS_SEPARATOR
+        )
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.minutes.is_none()
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TimeLog {
+    pub file: Option<String>,
+    pub dry_run: bool,
+    pub journal: Journal,
+}
+
+impl TimeLog {
+    fn read_text(&self) -> Result<String, Error> {
+        let mut text = String::new();
+        if let Some(ref path) = self.file {
+            if let Ok(mut file) = fs::File::open(path) {
+                file.read_to_string(&mut text)?;
+            }
+        }
+        Ok(text)
+    }
+
+    pub fn read_entries(&self) -> Result<Vec<TimeEntry>, Error> {
+        Ok(self.read_text()?.lines().filter_map(TimeEntry::parse).collect())
+    }
+
+    pub fn write_entries(&self, entries: &[TimeEntry]) -> Result<(), Error> {
+        if self.dry_run {
+            return Ok(());
+        }
+        if let Some(ref path) = self.file {
+            let mut text = String::new();
+            for entry in entries {
+                text += &entry.to_line();
+                text.push('\n');
+            }
+            let old_content = self.read_text()?;
+            fs::File::create(path)?.write_all(text.as_bytes())?;
+            self.journal.record(Action::Modify { path: path.clone(), old_content, new_content: text })?;
+        }
+        Ok(())
+    }
+
+    pub fn action_name(&self, done: &'static str, planned: &'static str) -> &'static str {
+        if self.dry_run { planned } else { done }
+    }
+
+    pub fn start(&self, id: &str, now: NaiveDateTime) -> Result<Option<TimeEntry>, Error> {
+        let stopped = self.stop(now)?;
+        self.add(TimeEntry { id: id.to_string(), start: now, minutes: None })?;
+        Ok(stopped)
+    }
+
+    pub fn stop(&self, now: NaiveDateTime) -> Result<Option<TimeEntry>, Error> {
+        let mut entries = self.read_entries()?;
+        let mut stopped = None;
+        for entry in entries.iter_mut().filter(|entry| entry.is_running()) {
+            entry.minutes = Some((now - entry.start).num_minutes().max(0));
+            stopped = Some(entry.clone());
+        }
+        if stopped.is_some() {
+            self.write_entries(&entries)?;
+        }
+        Ok(stopped)
+    }
+
+    pub fn add(&self, entry: TimeEntry) -> Result<(), Error> {
+        let mut entries = self.read_entries()?;
+        entries.push(entry);
+        self.write_entries(&entries)
+    }
+}
+
+pub fn parse_duration(input: &str) -> Option<i64> {
+    let input = input.trim().to_lowercase();
+    if input.is_empty() {
+        return None;
+    }
+    if let Ok(minutes) = input.parse::<i64>() {
+        return Some(minutes);
+    }
+
+    let mut minutes = 0.0;
+    let mut number = String::new();
+    for ch in input.chars() {
+        match ch {
+            '0'..='9' | '.' => number.push(ch),
+            'h' | 'm' if !number.is_empty() => {
+                let value = number.parse::<f64>().ok()?;
+                minutes += if ch == 'h' { value * 60.0 } else { value };
+                number.clear();
+            }
+            _ => return None,
+        }
+    }
+    if number.is_empty() {
+        Some(minutes.round() as i64)
+    } else {
+        None
+    }
+}
+
+pub fn format_duration(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h{}m", hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_duration() {
+        assert_eq!(Some(90), super::parse_duration("1h30m"));
+        assert_eq!(Some(90), super::parse_duration("1.5h"));
+        assert_eq!(Some(120), super::parse_duration("2h"));
+        assert_eq!(Some(45), super::parse_duration("45m"));
+        assert_eq!(Some(45), super::parse_duration("45"));
+        assert_eq!(None, super::parse_duration("h"));
+        assert_eq!(None, super::parse_duration("1h30"));
+        assert_eq!(None, super::parse_duration("1d"));
+        assert_eq!(None, super::parse_duration(""));
+    }
+
+    #[test]
+    fn format_duration() {
+        assert_eq!("1h30m", super::format_duration(90));
+        assert_eq!("2h", super::format_duration(120));
+        assert_eq!("45m", super::format_duration(45));
+        assert_eq!("0m", super::format_duration(0));
+    }
+
+    #[test]
+    fn time_entry() {
+        let line = "12\t2018-06-04 10:00:00\t90";
+        let entry = TimeEntry::parse(line).unwrap();
+        assert_eq!("12", entry.id);
+        assert_eq!(Some(90), entry.minutes);
+        assert_eq!(line, entry.to_line());
+
+        let entry = TimeEntry::parse("12\t2018-06-04 10:00:00\t").unwrap();
+        assert!(entry.is_running());
+
+        assert_eq!(None, TimeEntry::parse("12\t2018-06-04\t90"));
+        assert_eq!(None, TimeEntry::parse("text"));
+    }
+}
@@ -0,0 +1,230 @@
+use date::{self, Calendar};
+use todo::attrs::Attrs;
+use todo::exchange::{IssueRecord, PRIORITY_KEY, SCOPE_KEY};
+use todo::issue::Issue;
+
+pub const CONTEXT_KEY: &str = "ctx";
+const PRIORITY_TAG: &str = "pri";
+const ID_TAG: &str = "id";
+const DONE_MARK: &str = "x";
+// Words are separated by whitespace, so it is percent-encoded inside values
+const ESCAPES: [(&str, &str); 3] = [("%", "%25"), (" ", "%20"), ("\t", "%09")];
+
+fn encode_value(value: &str) -> String {
+    ESCAPES.iter().fold(value.to_string(), |value, &(raw, escaped)| value.replace(raw, escaped))
+}
+
+fn escape_char(ch: char) -> String {
+    format!("%{:02X}", ch as u32)
+}
+
+// Keys end at the first colon, so it is escaped as well
+fn encode_key(key: &str) -> String {
+    encode_value(key).replace(':', &escape_char(':'))
+}
+
+// Title words must not be read back as projects, contexts, tags or a leading mark
+fn encode_title_word(word: &str, is_first: bool) -> String {
+    let word = encode_value(word);
+    if parse_tag(&word).is_some() {
+        return word.replace(':', &escape_char(':'));
+    }
+    let is_mark = is_first && (word == DONE_MARK || parse_priority(&word).is_some() || is_date(&word));
+    let is_prefixed = word.len() > 1 && word.starts_with(|ch| ch == '+' || ch == '@');
+    match word.chars().next() {
+        Some(first) if is_mark || is_prefixed => escape_char(first) + &word[first.len_utf8()..],
+        _ => word,
+    }
+}
+
+fn decode_value(value: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = value;
+    while let Some(pos) = rest.find('%') {
+        decoded += &rest[..pos];
+        rest = &rest[pos..];
+        let byte = rest.get(1..3).and_then(|hex| u8::from_str_radix(hex, 16).ok()).filter(u8::is_ascii);
+        match byte {
+            Some(byte) => {
+                decoded.push(byte as char);
+                rest = &rest[3..];
+            }
+            None => {
+                decoded.push('%');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded + rest
+}
+
+fn is_date(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() == 10 && bytes.iter().enumerate().all(|(i, &b)| {
+        if i == 4 || i == 7 { b == b'-' } else { b.is_ascii_digit() }
+    })
+}
+
+fn parse_priority(text: &str) -> Option<char> {
+    let bytes = text.as_bytes();
+    if bytes.len() == 3 && bytes[0] == b'(' && bytes[2] == b')' && bytes[1].is_ascii_uppercase() {
+        Some(bytes[1] as char)
+    } else {
+        None
+    }
+}
+
+fn parse_tag(word: &str) -> Option<(&str, &str)> {
+    let pos = word.find(':')?;
+    let (key, value) = (&word[..pos], &word[(pos + 1)..]);
+    let is_key = !key.is_empty() && key.chars().all(|ch| ch.is_alphanumeric() || "_-%".contains(ch));
+    if is_key && !value.is_empty() && !value.starts_with("//") {
+        Some((key, value))
+    } else {
+        None
+    }
+}
+
+pub fn encode(record: &IssueRecord, issue: &Issue<String>) -> String {
+    let closed = record.attr(&issue.closed_attr_key).unwrap_or("");
+    let priority = record.attr(PRIORITY_KEY).unwrap_or("");
+    let is_priority_letter = priority.len() == 1 && priority.as_bytes()[0].is_ascii_uppercase();
+
+    let mut words = Vec::new();
+    if !closed.is_empty() {
+        words.push(format!("{} {}", DONE_MARK, closed));
+    } else if is_priority_letter {
+        words.push(format!("({})", priority));
+    }
+    if let Some(title) = record.attr(&issue.attrs.default_key) {
+        let title_words = title.split_whitespace().enumerate();
+        words.extend(title_words.map(|(i, word)| encode_title_word(word, i == 0)));
+    }
+
+    let skip_keys = [
+        issue.attrs.default_key.as_str(),
+        issue.closed_attr_key.as_str(),
+        PRIORITY_KEY,
+    ];
+    for &(ref key, ref value) in &record.attrs {
+        let values = Attrs::split_values(value);
+        if skip_keys.contains(&key.as_str()) {
+            continue;
+        } else if key == SCOPE_KEY {
+            words.push(format!("+{}", encode_value(value)));
+        } else if key == CONTEXT_KEY {
+            words.extend(values.iter().map(|value| format!("@{}", encode_value(value))));
+        } else {
+            let values: Vec<String> = values.iter().map(|value| encode_value(value)).collect();
+            words.push(format!("{}:{}", encode_key(key), values.join(&Attrs::VALUES_SEPARATOR.to_string())));
+        }
+    }
+    if !priority.is_empty() && (!closed.is_empty() || !is_priority_letter) {
+        words.push(format!("{}:{}", PRIORITY_TAG, encode_value(priority)));
+    }
+    if let Some(ref id) = record.id {
+        words.push(format!("{}:{}", ID_TAG, encode_value(id)));
+    }
+    words.join(" ")
+}
+
+fn add_value(record: &mut IssueRecord, key: &str, value: String) {
+    let mut values = Attrs::split_values(record.attr(key).unwrap_or(""));
+    values.push(value);
+    record.set_attr(key, &Attrs::join_values(&values));
+}
+
+pub fn decode(line: &str, issue: &Issue<String>, calendar: &Calendar) -> Option<IssueRecord> {
+    let mut words = line.split_whitespace().peekable();
+    words.peek()?;
+
+    let mut record = IssueRecord::default();
+    if words.peek() == Some(&DONE_MARK) {
+        words.next();
+        if let Some(date) = words.peek().cloned().filter(|word| is_date(word)) {
+            record.set_attr(&issue.closed_attr_key, date);
+            words.next();
+        } else {
+            record.set_attr(&issue.closed_attr_key, &date::format_date(calendar.today()));
+        }
+    } else if let Some(priority) = words.peek().and_then(|word| parse_priority(word)) {
+        record.set_attr(PRIORITY_KEY, &priority.to_string());
+        words.next();
+    }
+    // Creation date
+    if words.peek().map(|word| is_date(word)).unwrap_or(false) {
+        words.next();
+    }
+
+    let mut title = Vec::new();
+    for word in words {
+        if word.len() > 1 && word.starts_with('+') {
+            // The first project is the scope, the others are kept as tags
+            let key = if record.attr(SCOPE_KEY).is_some() { &issue.tags_attr_key } else { SCOPE_KEY };
+            add_value(&mut record, key, decode_value(&word[1..]));
+        } else if word.len() > 1 && word.starts_with('@') {
+            add_value(&mut record, CONTEXT_KEY, decode_value(&word[1..]));
+        } else if let Some((key, value)) = parse_tag(word) {
+            let values: Vec<String> = Attrs::split_values(value).iter().map(|value| decode_value(value)).collect();
+            match key {
+                ID_TAG => record.id = Some(decode_value(value)),
+                PRIORITY_TAG => record.set_attr(PRIORITY_KEY, &decode_value(value)),
+                _ => record.set_attr(&decode_value(key), &Attrs::join_values(&values)),
+            }
+        } else {
+            title.push(decode_value(word));
+        }
+    }
+    if !title.is_empty() {
+        let default_key = issue.attrs.default_key.to_string();
+        record.attrs.insert(0, (default_key, title.join(" ")));
+    }
+    Some(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use todo::issue::fixture::issue;
+
+    #[test]
+    fn decode_line() {
+        let calendar = Calendar::default();
+        let record = decode("(A) 2024-01-01 Call mom +family @phone @home due:2024-01-05 id:7", &issue(), &calendar).unwrap();
+        assert_eq!(Some("7".to_string()), record.id);
+        assert_eq!(Some("Call mom"), record.attr("name"));
+        assert_eq!(Some("A"), record.attr(PRIORITY_KEY));
+        assert_eq!(Some("family"), record.attr(SCOPE_KEY));
+        assert_eq!(Some("phone, home"), record.attr(CONTEXT_KEY));
+        assert_eq!(Some("2024-01-05"), record.attr("due"));
+
+        let record = decode("x 2024-01-03 2024-01-01 Pay bills pri:B", &issue(), &calendar).unwrap();
+        assert_eq!(Some("2024-01-03"), record.attr("closed"));
+        assert_eq!(Some("B"), record.attr(PRIORITY_KEY));
+        assert_eq!(Some("Pay bills"), record.attr("name"));
+
+        let record = decode("x Water plants estimate:1h%2030m", &issue(), &calendar).unwrap();
+        assert_eq!(Some(date::format_date(calendar.today()).as_str()), record.attr("closed"));
+        assert_eq!(Some("1h 30m"), record.attr("estimate"));
+
+        let record = decode("Move +home +garden +tools %2Bx 10%3A30", &issue(), &calendar).unwrap();
+        assert_eq!(Some("home"), record.attr(SCOPE_KEY));
+        assert_eq!(Some("garden, tools"), record.attr("tags"));
+        assert_eq!(Some("Move +x 10:30"), record.attr("name"));
+
+        assert!(decode("  ", &issue(), &calendar).is_none());
+    }
+
+    #[test]
+    fn encode_record() {
+        let issue = issue();
+        for line in &[
+            "(A) Call mom +family @phone @home due:2024-01-05 id:7",
+            "x 2024-01-03 Pay bills tags:a,b pri:B",
+            "Plan trip +big%20move note:50%25%20off,later",
+            "%78 Meet at 10%3A30 %2Bx %40y 100%25 my%20key:value",
+        ] {
+            assert_eq!(*line, encode(&decode(line, &issue, &Calendar::default()).unwrap(), &issue));
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use std::env;
+use std::process::Command;
+
+pub fn user_name(configured: Option<&String>) -> Option<String> {
+    if let Some(name) = configured {
+        if !name.trim().is_empty() {
+            return Some(name.trim().to_string());
+        }
+    }
+
+    let git_name = Command::new("git")
+        .args(&["config", "user.name"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    git_name.or_else(|| {
+        env::var("USER")
+            .or_else(|_| env::var("USERNAME"))
+            .ok()
+            .filter(|name| !name.is_empty())
+    })
+}
@@ -0,0 +1,82 @@
+#[macro_use]
+mod common;
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::{env, fs};
+
+#[test]
+fn new_issue_body() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_body")
+        .expect("Can't remove test_body dir");
+
+    //
+    // Test reading body from file
+    //
+
+    create_file!("target/test_body/notes.md", "# Notes\n\n- one");
+    assert_output!(
+        "todo new priority:B id:151 tags:a notes body:@target/test_body/notes.md" => "issues/B.151.notes.md"
+    );
+    assert_content!("issues/B.151.notes.md", "#[tags: a]\n# Notes\n\n- one\n");
+
+    assert_error!(
+        "todo new priority:B id:152 notes body:@target/test_body/missing.md" => (
+            1,
+            "error: Can't read body file: target/test_body/missing.md\n  caused by: No such file or directory (os error 2)"
+        )
+    );
+
+    //
+    // Test reading body from stdin
+    //
+
+    let mut child = Command::new(target_path!("todo"))
+        .args(&["new", "priority:B", "id:153", "crash", "body:-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed execute command `todo new`");
+    child.stdin.take()
+        .expect("Can't open stdin")
+        .write_all(b"panicked at main.rs\n")
+        .expect("Can't write stdin");
+    let output = child.wait_with_output().expect("Failed wait command `todo new`");
+    assert_eq!("issues/B.153.crash.md\n", String::from_utf8_lossy(&output.stdout));
+    assert_content!("issues/B.153.crash.md", "panicked at main.rs\n");
+
+    create_file!("target/test_body/crash.log", "panicked at lib.rs\n");
+    let output = Command::new(target_path!("todo"))
+        .args(&["new", "priority:B", "id:154", "crash"])
+        .stdin(fs::File::open("target/test_body/crash.log").expect("Can't open crash.log"))
+        .output()
+        .expect("Failed execute command `todo new`");
+    assert_eq!("issues/B.154.crash.md\n", String::from_utf8_lossy(&output.stdout));
+    assert_content!("issues/B.154.crash.md", "panicked at lib.rs\n");
+
+    let mut child = Command::new(target_path!("todo"))
+        .args(&["new", "priority:B", "id:155", "quiet"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed execute command `todo new`");
+    child.stdin.take()
+        .expect("Can't open stdin")
+        .write_all(b"unrelated pipe\n")
+        .expect("Can't write stdin");
+    let output = child.wait_with_output().expect("Failed wait command `todo new`");
+    assert_eq!("issues/B.155.quiet.md\n", String::from_utf8_lossy(&output.stdout));
+    assert_eq!(
+        "hint: piped input is not read, use `body:-` to take the body from stdin\n",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_content!("issues/B.155.quiet.md", "");
+
+    delete_file!("issues/B.151.notes.md");
+    delete_file!("issues/B.153.crash.md");
+    delete_file!("issues/B.154.crash.md");
+    delete_file!("issues/B.155.quiet.md");
+}
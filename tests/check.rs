@@ -0,0 +1,67 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn check_issues() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_check")
+        .expect("Can't remove test_check dir");
+
+    create_file!("target/test_check/issues/C.71.task1.md", "#[tags: a]\n- [ ] one\n- [x] two\n- [ ] three\n");
+    create_file!("target/test_check/issues/C.72.task2.md", "- [x] one\n");
+    create_file!("target/test_check/issues/C.73.task3.md", "text\n");
+
+    //
+    // Test checklist progress
+    //
+
+    assert_output!(
+        "todo list top:C columns:progress" => r#"
+1/3 target/test_check/issues/C.71.task1.md
+1/1 target/test_check/issues/C.72.task2.md
+- target/test_check/issues/C.73.task3.md
+"#
+    );
+
+    assert_output!(
+        "todo list top:C progress:open" => "target/test_check/issues/C.71.task1.md",
+        "todo list top:C progress:done" => "target/test_check/issues/C.72.task2.md",
+        "todo list top:C progress:1/3" => "target/test_check/issues/C.71.task1.md",
+        "todo list top:C progress:" => "target/test_check/issues/C.73.task3.md"
+    );
+
+    assert_output!(
+        "todo show 71" => r#"
+#[tags: a]
+- [ ] one
+- [x] two
+- [ ] three
+progress: 1/3
+"#
+    );
+
+    //
+    // Test ticking checklist items
+    //
+
+    assert_output!(
+        "todo check 71 1 -2" => "target/test_check/issues/C.71.task1.md"
+    );
+    assert_content!("target/test_check/issues/C.71.task1.md", "#[tags: a]\n- [x] one\n- [ ] two\n- [ ] three\n");
+
+    assert_output!(
+        "todo check 71 +2,3" => "target/test_check/issues/C.71.task1.md"
+    );
+    assert_content!("target/test_check/issues/C.71.task1.md", "#[tags: a]\n- [x] one\n- [x] two\n- [x] three\n");
+
+    assert_output!(
+        "todo check 71 4" => ""
+    );
+
+    delete_file!("target/test_check/issues/C.71.task1.md");
+    delete_file!("target/test_check/issues/C.72.task2.md");
+    delete_file!("target/test_check/issues/C.73.task3.md");
+}
@@ -0,0 +1,104 @@
+#[macro_use]
+mod common;
+extern crate chrono;
+
+use chrono::Local;
+use std::{env, fs};
+
+#[test]
+fn close_issues() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_close")
+        .expect("Can't remove test_close dir");
+
+    create_file!("target/test_close/todo.toml", r#"
+[store.fs]
+issues_dir = "issues"
+ext = "md"
+id_generator = "sequence"
+
+[generator.sequence]
+required = true
+file = "target/test_close/todo.seq"
+"#
+    );
+    env::set_var("TODO_CONFIG_FILE_NAME", "target/test_close/todo.toml");
+    create_file!("target/test_close/todo.seq", "40");
+
+    let today = Local::now().naive_local().date().format("%Y-%m-%d").to_string();
+
+    create_file!(
+        "target/test_close/issues/S.31.chore.md",
+        "#[due: 2018-06-04]\n#[recur: weekly]\ntext\n- [x] step\n\n## Comments\n\n### 2018-06-04 10:00:00 Tester\ndone\n"
+    );
+    create_file!("target/test_close/issues/S.32.task.md", "text\n");
+    create_file!("target/test_close/issues/S.33.report.md", "#[recur: monthly on 1]\n");
+
+    //
+    // Test closing issues
+    //
+
+    assert_output!(
+        "todo close 32" => "target/test_close/issues/S.32.task.md"
+    );
+    assert_content!("target/test_close/issues/S.32.task.md", (format!("#[closed: {}]\ntext\n", today)));
+
+    assert_output!(
+        "todo close 32" => ""
+    );
+
+    //
+    // Test creating the next recurring issue
+    //
+
+    assert_output!(
+        "todo close 31" => r#"
+target/test_close/issues/S.31.chore.md
+issues/S.40.chore.md
+"#
+    );
+    assert_content!(
+        "target/test_close/issues/S.31.chore.md",
+        (format!("#[due: 2018-06-04]\n#[recur: weekly]\n#[closed: {}]\ntext\n- [x] step\n\n## Comments\n\n### 2018-06-04 10:00:00 Tester\ndone\n", today))
+    );
+    assert_content!("issues/S.40.chore.md", "#[due: 2018-06-11]\n#[recur: weekly]\ntext\n- [x] step\n");
+
+    assert_output!(
+        "todo close 40" => r#"
+issues/S.40.chore.md
+issues/S.41.chore.md
+"#
+    );
+    assert_content!("issues/S.41.chore.md", "#[due: 2018-06-18]\n#[recur: weekly]\ntext\n- [x] step\n");
+
+    assert_output!(
+        "todo close 33" => r#"
+target/test_close/issues/S.33.report.md
+issues/S.42.report.md
+"#
+    );
+    let content = fs::read_to_string("issues/S.42.report.md")
+        .expect("Can't read issues/S.42.report.md");
+    assert!(content.starts_with("#[due: "));
+    assert!(content.contains("-01]\n#[recur: monthly on 1]\n"));
+
+    delete_file!("issues/S.40.chore.md");
+    delete_file!("issues/S.41.chore.md");
+    delete_file!("issues/S.42.report.md");
+    delete_file!("target/test_close/issues/S.31.chore.md");
+    delete_file!("target/test_close/issues/S.32.task.md");
+    delete_file!("target/test_close/issues/S.33.report.md");
+    env::remove_var("TODO_CONFIG_FILE_NAME");
+
+    //
+    // Test recurring issue without id generator
+    //
+
+    create_file!("target/test_close/issues/S.34.standup.md", "#[recur: daily]\n");
+    assert_error!(
+        "todo close 34" => (5, "error: can't create the next recurring issue of `34` without id generator")
+    );
+    assert_content!("target/test_close/issues/S.34.standup.md", "#[recur: daily]\n");
+    delete_file!("target/test_close/issues/S.34.standup.md");
+}
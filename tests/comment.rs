@@ -0,0 +1,48 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn comment_issues() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    env::set_var("TODO_SET_USER", "Tester");
+    fs::remove_dir_all("target/test_comment")
+        .expect("Can't remove test_comment dir");
+
+    create_file!("target/test_comment/issues/M.91.task1.md", "#[tags: a]\ntext\n");
+
+    //
+    // Test appending comments
+    //
+
+    assert_output!(
+        "todo comment 91 \"first comment\"" => "target/test_comment/issues/M.91.task1.md",
+        "todo comment 91 second comment" => "target/test_comment/issues/M.91.task1.md",
+        "todo comment 91" => ""
+    );
+
+    let content = fs::read_to_string("target/test_comment/issues/M.91.task1.md")
+        .expect("Can't read target/test_comment/issues/M.91.task1.md");
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(10, lines.len());
+    assert_eq!(["#[tags: a]", "text", "", "## Comments", ""], lines[..5]);
+    assert!(lines[5].starts_with("### 20") && lines[5].ends_with(" Tester"));
+    assert_eq!(["first comment", ""], lines[6..8]);
+    assert!(lines[8].starts_with("### 20") && lines[8].ends_with(" Tester"));
+    assert_eq!("second comment", lines[9]);
+
+    //
+    // Test rendering comments
+    //
+
+    let first_header = format!("  {}:", &lines[5][4..]);
+    let output = format!("#[tags: a]\ntext\ncomments:\n{}\n    first comment\n{}\n    second comment\n",
+        first_header, format!("  {}:", &lines[8][4..]));
+    assert_output!(
+        "todo show 91" => output
+    );
+
+    delete_file!("target/test_comment/issues/M.91.task1.md");
+}
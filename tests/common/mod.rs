@@ -29,6 +29,16 @@ macro_rules! create_file {
     }};
 }
 
+// Keeps generated content predictable for tests that don't check the stamps
+#[macro_export]
+macro_rules! disable_stamps {
+    () => {
+        for key in &["CREATED_ATTR", "AUTHOR_ATTR", "UPDATED_ATTR"] {
+            ::std::env::set_var(format!("TODO_SET_COMMAND.NEW.{}", key), "");
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! run {
     ($($command:tt),*) => {
@@ -169,3 +179,29 @@ macro_rules! assert_content {
         assert_eq!($content, contents);
     }};
 }
+
+#[macro_export]
+macro_rules! assert_error {
+    ($($command:tt => ($code:expr, $err:tt)),*) => {
+        $(
+            {
+                use ::std::process::Command;
+
+                let args = ::common::split_args($command);
+                let cmd = &args[0];
+                let mut cmd = Command::new(target_path!(cmd));
+                for arg in args[1..].iter() {
+                    cmd.arg(&arg);
+                }
+                println!("command: {:?}", cmd);
+                let output = cmd.output().expect(&format!("Failed execute command `{}`", $command));
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                println!("{}", output.status);
+                println!("{}", stderr);
+
+                assert_eq!(Some($code), output.status.code());
+                assert_eq!($err, stderr.trim());
+            }
+        )*
+    };
+}
@@ -0,0 +1,67 @@
+#[macro_use]
+mod common;
+
+use std::path::Path;
+use std::{env, fs};
+
+#[test]
+fn dry_run() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    env::set_var("TODO_SET_JOURNAL.FILE", "target/test_dry_run/todo.journal");
+    env::set_var("TODO_SET_TIME.LOG_FILE", "target/test_dry_run/todo.time");
+    env::set_var("TODO_SET_HISTORY.FILE", "target/test_dry_run/todo.history");
+    fs::remove_dir_all("target/test_dry_run")
+        .expect("Can't remove test_dry_run dir");
+
+    create_file!("target/test_dry_run/todo.toml", r#"
+[store.fs]
+issues_dir = "issues"
+ext = "md"
+id_generator = "sequence"
+
+[command.new.default_attrs]
+due = "2018-06-04"
+
+[generator.sequence]
+required = true
+file = "target/test_dry_run/todo.seq"
+"#
+    );
+    env::set_var("TODO_CONFIG_FILE_NAME", "target/test_dry_run/todo.toml");
+    create_file!("target/test_dry_run/todo.seq", "120");
+    create_file!("target/test_dry_run/issues/R.111.task.md", "#[tags: a]\ntext\n");
+
+    assert_output!(
+        "todo --dry-run new priority:R chore" => r#"
+would generate id 120
+would create issues/R.120.chore.md
+#[due: 2018-06-04]
+"#,
+        "todo new priority:R chore --dry-run" => r#"
+would generate id 120
+would create issues/R.120.chore.md
+#[due: 2018-06-04]
+"#
+    );
+    assert!(!Path::new("issues/R.120.chore.md").exists());
+    assert_content!("target/test_dry_run/todo.seq", "120");
+
+    assert_output!(
+        "todo --dry-run tag 111 +b -a" => "would update target/test_dry_run/issues/R.111.task.md\n  tags: a -> b\n",
+        "todo --dry-run start 111" => "would start 111",
+        "todo --dry-run log 111 1h" => "would log 111 1h"
+    );
+    assert_content!("target/test_dry_run/issues/R.111.task.md", "#[tags: a]\ntext\n");
+    assert!(!Path::new("target/test_dry_run/todo.time").exists());
+    assert!(!Path::new("target/test_dry_run/todo.journal").exists());
+
+    assert_output!(
+        "todo tag 111 +c" => "target/test_dry_run/issues/R.111.task.md",
+        "todo --dry-run undo" => "would restore target/test_dry_run/todo.history\nwould restore target/test_dry_run/issues/R.111.task.md\n"
+    );
+    assert_content!("target/test_dry_run/issues/R.111.task.md", "#[tags: a, c]\ntext\n");
+
+    env::remove_var("TODO_CONFIG_FILE_NAME");
+    delete_file!("target/test_dry_run/issues/R.111.task.md");
+}
@@ -0,0 +1,98 @@
+#[macro_use]
+mod common;
+extern crate chrono;
+
+use chrono::{Datelike, Duration, Local};
+use std::{env, fs};
+
+#[test]
+fn due_issues() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_due")
+        .expect("Can't remove test_due dir");
+
+    let today = Local::now().naive_local().date();
+    let date = |days| (today + Duration::days(days)).format("%Y-%m-%d").to_string();
+
+    //
+    // Test due date normalization
+    //
+
+    run!("todo new due:tomorrow task_due");
+    assert_content!("issues/task_due.md", (format!("#[due: {}]\n", date(1))));
+    delete_file!("issues/task_due.md");
+
+    run!("todo new due:+3d task_due");
+    assert_content!("issues/task_due.md", (format!("#[due: {}]\n", date(3))));
+    delete_file!("issues/task_due.md");
+
+    run!("todo new due:2018-06-04 task_due");
+    assert_content!("issues/task_due.md", "#[due: 2018-06-04]\n");
+    delete_file!("issues/task_due.md");
+
+    run!("todo new due:someday task_due");
+    assert!(fs::File::open("issues/task_due.md").is_err());
+
+    //
+    // Test filtering by due date
+    //
+
+    create_file!("target/test_due/issues/A.1.task1.md", (format!("#[due: {}]\n", date(-1))));
+    create_file!("target/test_due/issues/B.2.task2.md", (format!("#[due: {}]\n", date(0))));
+    create_file!("target/test_due/issues/C.3.task3.md", (format!("#[due: {}]\n", date(3))));
+    create_file!("target/test_due/issues/D.4.task4.md", (format!("#[due: {}]\n", date(30))));
+    create_file!("target/test_due/issues/E.5.task5.md", "");
+
+    assert_output!(
+        "todo list due:overdue" => "target/test_due/issues/A.1.task1.md",
+        "todo list due:today" => "target/test_due/issues/B.2.task2.md",
+        "todo list due:+30d" => "target/test_due/issues/D.4.task4.md",
+        "todo list due:" => "target/test_due/issues/E.5.task5.md"
+    );
+
+    assert_output!(
+        "todo list due:upcoming" => r#"
+target/test_due/issues/B.2.task2.md
+target/test_due/issues/C.3.task3.md
+"#
+    );
+
+    assert_output!(
+        (&format!("todo list due:{} top:D", date(30))) => "target/test_due/issues/D.4.task4.md"
+    );
+
+    //
+    // Test agenda
+    //
+
+    let is_task3_this_week = 3 + today.weekday().num_days_from_monday() <= 6;
+    let mut week = "target/test_due/issues/B.2.task2.md\n".to_string();
+    if is_task3_this_week {
+        week += "target/test_due/issues/C.3.task3.md\n";
+    }
+    assert_output!("todo list due:week" => week);
+
+    let mut agenda = format!(
+        "{} target/test_due/issues/A.1.task1.md\n{} target/test_due/issues/B.2.task2.md\n",
+        date(-1),
+        date(0)
+    );
+    if is_task3_this_week {
+        agenda += &format!("{} target/test_due/issues/C.3.task3.md\n", date(3));
+    }
+    assert_output!("todo agenda" => agenda);
+
+    let sorted = format!(
+        "{} C target/test_due/issues/C.3.task3.md\n{} B target/test_due/issues/B.2.task2.md",
+        date(3),
+        date(0)
+    );
+    assert_output!("todo list sort:-due columns:due,priority due:upcoming" => sorted);
+
+    delete_file!("target/test_due/issues/A.1.task1.md");
+    delete_file!("target/test_due/issues/B.2.task2.md");
+    delete_file!("target/test_due/issues/C.3.task3.md");
+    delete_file!("target/test_due/issues/D.4.task4.md");
+    delete_file!("target/test_due/issues/E.5.task5.md");
+}
@@ -0,0 +1,87 @@
+#[macro_use]
+mod common;
+extern crate chrono;
+
+use chrono::{Duration, Local};
+use std::path::Path;
+use std::{env, fs};
+
+#[test]
+fn edit_issues() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_edit")
+        .expect("Can't remove test_edit dir");
+
+    //
+    // Test creating issue in editor
+    //
+
+    env::set_var("TODO_SET_EDITOR", "sed -i s/^$/body/");
+    assert_output!(
+        "todo new -e priority:V id:141 tags:a task" => "issues/V.141.task.md",
+        "todo new --edit priority:V id:142 task" => "issues/V.142.task.md"
+    );
+    assert_content!("issues/V.141.task.md", "#[tags: a]\nbody\n");
+    assert_content!("issues/V.142.task.md", "body\n");
+
+    env::set_var("TODO_SET_EDITOR", "false");
+    assert_error!(
+        "todo new -e priority:V id:143 task" => (1, "error: editor `false` exited with error")
+    );
+    assert!(!Path::new("issues/V.143.task.md").exists());
+
+    //
+    // Test taking id from sequence after editing
+    //
+
+    env::set_var("TODO_SET_STORE.FS.ID_GENERATOR", "sequence");
+    env::set_var("TODO_SET_GENERATOR.SEQUENCE.FILE", "target/test_edit/todo.seq");
+    create_file!("target/test_edit/todo.seq", "146");
+    assert_error!(
+        "todo new -e priority:V task" => (1, "error: editor `false` exited with error")
+    );
+    assert_content!("target/test_edit/todo.seq", "146");
+
+    env::set_var("TODO_SET_EDITOR", "sed -i s/^$/#[name:renamed]/");
+    assert_output!(
+        "todo new -e priority:V tags:b task" => "issues/V.146.renamed.md"
+    );
+    assert_content!("issues/V.146.renamed.md", "#[tags: b]\n");
+    assert_content!("target/test_edit/todo.seq", "147");
+    env::remove_var("TODO_SET_STORE.FS.ID_GENERATOR");
+    env::remove_var("TODO_SET_GENERATOR.SEQUENCE.FILE");
+
+    //
+    // Test editing existing issue
+    //
+
+    create_file!("target/test_edit/issues/V.144.task.md", "#[due: 2018-06-04]\ntext\n");
+
+    env::set_var("TODO_SET_EDITOR", "sed -i s/text/edited/");
+    assert_output!(
+        "todo edit 144" => "target/test_edit/issues/V.144.task.md"
+    );
+    assert_content!("target/test_edit/issues/V.144.task.md", "#[due: 2018-06-04]\nedited\n");
+
+    env::set_var("TODO_SET_EDITOR", "sed -i s/2018-06-04/tomorrow/");
+    assert_output!(
+        "todo edit 144" => "target/test_edit/issues/V.144.task.md"
+    );
+    let tomorrow = (Local::now().naive_local().date() + Duration::days(1)).format("%Y-%m-%d");
+    let content = format!("#[due: {}]\nedited\n", tomorrow);
+    assert_content!("target/test_edit/issues/V.144.task.md", content);
+
+    create_file!("target/test_edit/issues/V.144.task.md", "#[due: 2018-06-04]\nedited\n");
+    env::set_var("TODO_SET_EDITOR", "sed -i s/2018-06-04/someday/");
+    assert_error!(
+        "todo edit 144" => (2, "error: invalid date `someday`")
+    );
+    assert_content!("target/test_edit/issues/V.144.task.md", "#[due: 2018-06-04]\nedited\n");
+
+    env::remove_var("TODO_SET_EDITOR");
+    delete_file!("issues/V.141.task.md");
+    delete_file!("issues/V.142.task.md");
+    delete_file!("issues/V.146.renamed.md");
+    delete_file!("target/test_edit/issues/V.144.task.md");
+}
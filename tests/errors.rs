@@ -0,0 +1,58 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn report_errors() {
+    env::set_var("TODO_HOME", "./");
+    fs::remove_dir_all("target/test_errors")
+        .expect("Can't remove test_errors dir");
+
+    create_file!("target/test_errors/issues/E.131.task.md", "#[tags: a]\n");
+
+    assert_error!(
+        "todo tag +b" => (2, "error: `id` is not specified"),
+        "todo list (" => (2, "error: invalid regular expression `(`"),
+        "todo log 131 soon" => (2, "error: invalid duration `soon`"),
+        "todo start 139" => (3, "error: issue `139` is not found"),
+        "todo tag 139 +x" => (3, "error: issue `139` is not found"),
+        "todo check 139 1" => (3, "error: issue `139` is not found"),
+        "todo comment 139 hi" => (3, "error: issue `139` is not found"),
+        "todo close 139" => (3, "error: issue `139` is not found"),
+        "todo show 139" => (3, "error: issue `139` is not found"),
+        "todo edit 139" => (3, "error: issue `139` is not found"),
+        "todo link 131 blocks 131" => (4, "error: linking `131` to `131` makes a dependency cycle")
+    );
+
+    create_file!("target/test_errors/todo.toml", r#"
+[store.fs]
+issues_dir = "target/test_errors/issues"
+"#
+    );
+    env::set_var("TODO_CONFIG_FILE_NAME", "target/test_errors/todo.toml");
+    assert_error!(
+        "todo new priority:E id:131 task" => (4, "error: file `target/test_errors/issues/E.131.task.md` already exists")
+    );
+
+    env::set_var("TODO_SET_STORE.FS.ID_GENERATOR", "uuid");
+    assert_error!(
+        "todo list" => (5, "error: unsupported generator type `uuid`")
+    );
+    env::remove_var("TODO_SET_STORE.FS.ID_GENERATOR");
+
+    env::set_var("TODO_SET_TIMEZONE", "Europe/Berlin");
+    assert_error!(
+        "todo list" => (5, "error: unsupported timezone `Europe/Berlin`, only fixed offsets like `+02:00` or `utc` are supported")
+    );
+    env::remove_var("TODO_SET_TIMEZONE");
+
+    env::set_var("TODO_SET_COMMAND.NEW.DEFAULT_ATTRS.DUE", "someday");
+    assert_error!(
+        "todo list" => (2, "error: invalid date `someday`")
+    );
+    env::remove_var("TODO_SET_COMMAND.NEW.DEFAULT_ATTRS.DUE");
+    env::remove_var("TODO_CONFIG_FILE_NAME");
+
+    delete_file!("target/test_errors/issues/E.131.task.md");
+}
@@ -0,0 +1,83 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn export_import_issues() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_exchange")
+        .expect("Can't remove test_exchange dir");
+
+    create_file!("target/test_exchange/issues/J.201.task.md", "#[tags: a, b]\n#[estimate: 2h]\ntext body\n");
+    create_file!("target/test_exchange/issues/J.202.other.md", "---\nstatus: open\n---\nyaml body\n");
+
+    assert_output!(
+        "todo export J.201" => r#"[
+  {
+    "id": "201",
+    "attrs": {
+      "priority": "J",
+      "name": "task",
+      "tags": "a, b",
+      "estimate": "2h"
+    },
+    "body": "text body\n"
+  }
+]"#,
+        "todo export J.202" => r#"[
+  {
+    "id": "202",
+    "attrs": {
+      "priority": "J",
+      "name": "other",
+      "status": "open"
+    },
+    "body": "yaml body\n"
+  }
+]"#
+    );
+
+    create_file!("target/test_exchange/todo.toml", r#"
+[store.fs]
+issues_dir = "target/test_exchange/imported"
+attrs_style = "yaml"
+id_generator = "sequence"
+
+[generator.sequence]
+file = "target/test_exchange/todo.seq"
+"#
+    );
+    env::set_var("TODO_CONFIG_FILE_NAME", "target/test_exchange/todo.toml");
+    create_file!("target/test_exchange/todo.seq", "5");
+    create_file!("target/test_exchange/issues.json", r#"[
+  {"attrs": {"name": "fresh"}},
+  {"id": "201", "attrs": {"priority": "J", "name": "task", "ext": "md", "tags": "a, b"}, "body": "text body\n"}
+]"#
+    );
+
+    assert_output!(
+        "todo import target/test_exchange/issues.json" =>
+            "target/test_exchange/imported/202.fresh.md\ntarget/test_exchange/imported/J.201.task.md"
+    );
+    assert_content!("target/test_exchange/imported/J.201.task.md", "---\ntags: a, b\n---\ntext body\n");
+    assert_content!("target/test_exchange/todo.seq", "203");
+
+    assert_error!(
+        "todo import target/test_exchange/issues.json" =>
+            (4, "error: file `target/test_exchange/imported/J.201.task.md` already exists")
+    );
+    assert_content!("target/test_exchange/todo.seq", "203");
+
+    assert_error!(
+        "todo import target/test_exchange/missing.json" =>
+            (1, "error: Can't read import file: target/test_exchange/missing.json\n  caused by: No such file or directory (os error 2)")
+    );
+    env::remove_var("TODO_CONFIG_FILE_NAME");
+
+    delete_file!("target/test_exchange/imported/J.201.task.md");
+    delete_file!("target/test_exchange/imported/202.fresh.md");
+    delete_file!("target/test_exchange/issues/J.201.task.md");
+    delete_file!("target/test_exchange/issues/J.202.other.md");
+}
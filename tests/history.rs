@@ -0,0 +1,48 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn issue_history() {
+    env::set_var("TODO_HOME", "./");
+    env::set_var("TODO_SET_USER", "Tester");
+    env::set_var("TODO_SET_HISTORY.FILE", "target/test_history/todo.history");
+    fs::remove_dir_all("target/test_history")
+        .expect("Can't remove test_history dir");
+
+    create_file!("target/test_history/issues/H.101.task1.md", "#[tags: a]\n");
+    create_file!("target/test_history/issues/H.102.task2.md", "");
+
+    assert_output!(
+        "todo tag 101 +b -a" => "target/test_history/issues/H.101.task1.md",
+        "todo close 101" => "target/test_history/issues/H.101.task1.md",
+        "todo tag 102 x" => "target/test_history/issues/H.102.task2.md"
+    );
+
+    let content = fs::read_to_string("target/test_history/todo.history")
+        .expect("Can't read target/test_history/todo.history");
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(3, lines.len());
+    assert!(lines[0].ends_with("\tTester\t101\ttags\ta\tb"));
+    assert!(lines[1].contains("\tTester\t101\tclosed\t\t20"));
+    assert!(lines[2].ends_with("\tTester\t102\ttags\t\tx"));
+
+    let history = |line: &str| {
+        let fields: Vec<&str> = line.split('\t').collect();
+        format!(
+            "{} {} {}: {} -> {}",
+            fields[0], fields[1], fields[3],
+            if fields[4].is_empty() { "-" } else { fields[4] },
+            fields[5]
+        )
+    };
+    let output = format!("{}\n{}\n", history(lines[0]), history(lines[1]));
+    assert_output!(
+        "todo history 101" => output,
+        "todo history 103" => ""
+    );
+
+    delete_file!("target/test_history/issues/H.101.task1.md");
+    delete_file!("target/test_history/issues/H.102.task2.md");
+}
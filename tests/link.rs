@@ -0,0 +1,81 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn link_issues() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_link")
+        .expect("Can't remove test_link dir");
+
+    create_file!("target/test_link/issues/L.51.design.md", "text\n");
+    create_file!("target/test_link/issues/L.52.build.md", "#[blocked_by: 51]\ntext\n");
+    create_file!("target/test_link/issues/L.53.test.md", "");
+    create_file!("target/test_link/issues/L.54.release.md", "#[closed: 2018-06-04]\n");
+
+    //
+    // Test linking issues
+    //
+
+    assert_output!(
+        "todo link 52 blocks 53" => r#"
+target/test_link/issues/L.52.build.md
+target/test_link/issues/L.53.test.md
+"#
+    );
+    assert_content!("target/test_link/issues/L.52.build.md", "#[blocked_by: 51]\n#[blocks: 53]\ntext\n");
+    assert_content!("target/test_link/issues/L.53.test.md", "#[blocked_by: 52]\n");
+
+    assert_output!(
+        "todo link 51 blocked_by 54" => r#"
+target/test_link/issues/L.54.release.md
+target/test_link/issues/L.51.design.md
+"#
+    );
+    assert_content!("target/test_link/issues/L.51.design.md", "#[blocked_by: 54]\ntext\n");
+
+    assert_output!(
+        "todo link 53 blocks 51" => "",
+        "todo link 52 blocks 52" => "",
+        "todo link 52 blocks 99" => ""
+    );
+    assert_content!("target/test_link/issues/L.51.design.md", "#[blocked_by: 54]\ntext\n");
+
+    //
+    // Test listing ready issues
+    //
+
+    assert_output!(
+        "todo list ready top:L" => "target/test_link/issues/L.51.design.md"
+    );
+
+    //
+    // Test showing dependency chain
+    //
+
+    assert_output!(
+        "todo show 53" => r#"
+#[blocked_by: 52]
+blocked_by:
+  52 build
+    51 design
+      54 release [closed]
+"#,
+        "todo show 51" => r#"
+#[blocked_by: 54]
+text
+blocked_by:
+  54 release [closed]
+blocks:
+  52 build
+    53 test
+"#
+    );
+
+    delete_file!("target/test_link/issues/L.51.design.md");
+    delete_file!("target/test_link/issues/L.52.build.md");
+    delete_file!("target/test_link/issues/L.53.test.md");
+    delete_file!("target/test_link/issues/L.54.release.md");
+}
@@ -6,6 +6,7 @@ use std::{env, fs};
 #[test]
 fn list_issues() {
     env::set_var("TODO_HOME", "./");
+    disable_stamps!();
     fs::remove_dir_all("target/test_list")
         .expect("Can't remove test_list dir");
 
@@ -250,4 +251,131 @@ target/test_list/issues/set/A.3.some task.md
     delete_file!("target/test_list/issues/set/A.3.some task.md");
     delete_file!("target/test_list/issues/set/B.4.other task.md");
     delete_file!("target/test_list/issues/.fin/task3.md");
+
+    //
+    // Test reading attrs from the issue header only
+    //
+
+    create_file!("target/test_list/issues/task1.md", "#[ctx: test]\n\n#[allow: user]\nlog\n#[ctx: log]\n");
+    create_file!("target/test_list/issues/new/task2.md", "log\n\n\n\n#[ctx: log]\n");
+
+    assert_output!(
+        [
+            "todo list ctx:log",
+            "todo --list ctx:log",
+            "todo -l ctx:log"
+        ] => r#"
+target/test_list/issues/task1.md
+target/test_list/issues/new/task2.md
+"#
+    );
+
+    assert_output!(
+        [
+            "todo list ctx:log header_only:true",
+            "todo -l ctx:log header_limit:4"
+        ] => ""
+    );
+
+    assert_output!(
+        [
+            "todo list allow:user header_only:true",
+            "todo -l allow:user header_limit:3"
+        ] => "target/test_list/issues/task1.md"
+    );
+
+    assert_output!(
+        "todo list allow:user header_limit:2" => ""
+    );
+
+    fs::write("target/test_list/issues/task3.md", b"#[ctx: bin]\n\xff\xfe\n")
+        .expect("Can't write task3.md");
+    assert_output!(
+        "todo list ctx:bin header_only:true columns:ctx" => "bin target/test_list/issues/task3.md"
+    );
+    delete_file!("target/test_list/issues/task3.md");
+
+    delete_file!("target/test_list/issues/task1.md");
+    delete_file!("target/test_list/issues/new/task2.md");
+
+    //
+    // Test filtering by front matter attrs
+    //
+
+    create_file!("target/test_list/issues/task1.md", "---\nctx: test\nallow: \"user\"\n---\n");
+    create_file!("target/test_list/issues/new/task2.md", "+++\nctx = \"test\"\n+++\n#[allow: root]\n");
+    create_file!("target/test_list/issues/set/task3.md", "#[ctx: test]\n#[allow: user]\n");
+
+    assert_output!(
+        [
+            "todo list ctx:test",
+            "todo -l ctx:test"
+        ] => r#"
+target/test_list/issues/task1.md
+target/test_list/issues/new/task2.md
+target/test_list/issues/set/task3.md
+"#
+    );
+
+    assert_output!(
+        [
+            "todo list ctx:test allow:user",
+            "todo -l ctx:test allow:user"
+        ] => r#"
+target/test_list/issues/task1.md
+target/test_list/issues/set/task3.md
+"#
+    );
+
+    assert_output!(
+        "todo list allow:root" => "target/test_list/issues/new/task2.md"
+    );
+
+    delete_file!("target/test_list/issues/task1.md");
+    delete_file!("target/test_list/issues/new/task2.md");
+    delete_file!("target/test_list/issues/set/task3.md");
+
+    //
+    // Test filtering by multi-valued attrs
+    //
+
+    create_file!("target/test_list/issues/task1.md", "#[tags: a, b]\n");
+    create_file!("target/test_list/issues/new/task2.md", "#[tags: a]\n#[tags: c]\n");
+    create_file!("target/test_list/issues/set/task3.md", "");
+
+    assert_output!(
+        [
+            "todo list tags:a",
+            "todo list tags:a,b",
+            "todo -l tags:b,c"
+        ] => r#"
+target/test_list/issues/task1.md
+target/test_list/issues/new/task2.md
+"#
+    );
+
+    assert_output!(
+        [
+            "todo list tags:a+b",
+            "todo -l tags:\"(a, c) + b\""
+        ] => "target/test_list/issues/task1.md"
+    );
+
+    assert_output!(
+        [
+            "todo list tags:a+^b",
+            "todo -l tags:c"
+        ] => "target/test_list/issues/new/task2.md"
+    );
+
+    assert_output!(
+        [
+            "todo list tags:",
+            "todo -l tags:^a"
+        ] => "target/test_list/issues/set/task3.md"
+    );
+
+    delete_file!("target/test_list/issues/task1.md");
+    delete_file!("target/test_list/issues/new/task2.md");
+    delete_file!("target/test_list/issues/set/task3.md");
 }
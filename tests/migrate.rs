@@ -0,0 +1,74 @@
+#[macro_use]
+mod common;
+
+use std::path::Path;
+use std::{env, fs};
+
+#[test]
+fn migrate_format() {
+    env::set_var("TODO_HOME", "./");
+    env::set_var("TODO_SET_JOURNAL.FILE", "target/test_migrate/todo.journal");
+    fs::remove_dir_all("target/test_migrate")
+        .expect("Can't remove test_migrate dir");
+
+    create_file!("target/test_migrate/issues/backend/I.191.task.md", "#[tags: a]\ntext\n");
+    create_file!("target/test_migrate/issues/I.192.other.md", "#[priority: I]\n");
+
+    assert_output!(
+        "todo --dry-run migrate test_migrate --to-format {id:.}{name}{.:ext}" =>
+            "would move target/test_migrate/issues/backend/I.191.task.md to target/test_migrate/issues/191.task.md
+  + #[scope: backend]
+  + #[priority: I]
+would move target/test_migrate/issues/I.192.other.md to target/test_migrate/issues/192.other.md"
+    );
+    assert!(Path::new("target/test_migrate/issues/backend/I.191.task.md").exists());
+
+    assert_output!(
+        "todo migrate test_migrate --to-format {id:.}{name}{.:ext}" =>
+            "target/test_migrate/issues/191.task.md\ntarget/test_migrate/issues/192.other.md"
+    );
+    assert_content!("target/test_migrate/issues/191.task.md", "#[tags: a]\n#[scope: backend]\n#[priority: I]\ntext\n");
+    assert_content!("target/test_migrate/issues/192.other.md", "#[priority: I]\n");
+    assert!(!Path::new("target/test_migrate/issues/backend").exists());
+
+    assert_output!(
+        "todo migrate test_migrate --from-format {id:.}{name}{.:ext} --to-format {id}-{name}{.:ext}" =>
+            "target/test_migrate/issues/191-task.md\ntarget/test_migrate/issues/192-other.md",
+        "todo undo" =>
+            "moved target/test_migrate/issues/191-task.md back to target/test_migrate/issues/191.task.md
+moved target/test_migrate/issues/192-other.md back to target/test_migrate/issues/192.other.md"
+    );
+
+    create_file!("target/test_migrate/issues/193.task.md", "");
+    assert_error!(
+        "todo migrate test_migrate --from-format {id:.}{name}{.:ext} --to-format {name}{.:ext}" =>
+            (4, "error: file `target/test_migrate/issues/task.md` already exists"),
+        "todo migrate test_migrate" => (2, "error: `to-format` is not specified")
+    );
+
+    create_file!("target/test_migrate/issues/I.inline.md", "#[id: 194]\ntext\n");
+    assert_output!(
+        "todo --dry-run migrate test_migrate/issues/I.inline --from-format {priority:.}{name}{.:ext} --to-format {id:.}{priority:.}{name}{.:ext}" =>
+            "would move target/test_migrate/issues/I.inline.md to target/test_migrate/issues/194.I.inline.md
+  - #[id: 194]",
+        "todo migrate test_migrate/issues/I.inline --from-format {priority:.}{name}{.:ext} --to-format {id:.}{priority:.}{name}{.:ext}" =>
+            "target/test_migrate/issues/194.I.inline.md"
+    );
+    assert_content!("target/test_migrate/issues/194.I.inline.md", "text\n");
+
+    create_file!("target/test_migrate/issues/I.bare.md", "");
+    assert_output!(
+        "todo migrate test_migrate/issues/I.bare --from-format {priority:.}{name}{.:ext} --to-format {id:.}{priority:.}{name}{.:ext}" => ""
+    );
+    assert_content!("target/test_migrate/issues/I.bare.md", "");
+    assert_output!(
+        "todo migrate test_migrate/issues/I.bare --from-format {id}-{name}.txt --to-format {name}{.:ext}" =>
+            "skipped target/test_migrate/issues/I.bare.md: path doesn't match the capture regex"
+    );
+
+    delete_file!("target/test_migrate/issues/194.I.inline.md");
+    delete_file!("target/test_migrate/issues/I.bare.md");
+    delete_file!("target/test_migrate/issues/191.task.md");
+    delete_file!("target/test_migrate/issues/192.other.md");
+    delete_file!("target/test_migrate/issues/193.task.md");
+}
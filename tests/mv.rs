@@ -0,0 +1,56 @@
+#[macro_use]
+mod common;
+
+use std::path::Path;
+use std::{env, fs};
+
+#[test]
+fn move_issues() {
+    env::set_var("TODO_HOME", "./");
+    env::set_var("TODO_SET_JOURNAL.FILE", "target/test_mv/todo.journal");
+    env::set_var("TODO_SET_HISTORY.FILE", "target/test_mv/todo.history");
+    env::set_var("TODO_SET_USER", "Tester");
+    fs::remove_dir_all("target/test_mv")
+        .expect("Can't remove test_mv dir");
+
+    create_file!("target/test_mv/issues/M.171.task.md", "#[tags: a]\ntext\n");
+    create_file!("target/test_mv/issues/M.172.other.md", "");
+
+    assert_output!(
+        "todo mv 171 scope:backend name:\"new title\"" => "target/test_mv/issues/backend/M.171.new title.md"
+    );
+    assert!(!Path::new("target/test_mv/issues/M.171.task.md").exists());
+    assert_content!("target/test_mv/issues/backend/M.171.new title.md", "#[tags: a]\ntext\n");
+
+    assert_output!(
+        "todo mv 171 s:frontend/ui" => "target/test_mv/issues/frontend/ui/M.171.new title.md"
+    );
+    assert!(!Path::new("target/test_mv/issues/backend").exists());
+
+    let content = fs::read_to_string("target/test_mv/todo.history")
+        .expect("Can't read target/test_mv/todo.history");
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(3, lines.len());
+    assert!(lines[0].ends_with("\tTester\t171\tscope\t\tbackend"));
+    assert!(lines[1].ends_with("\tTester\t171\tname\ttask\tnew title"));
+    assert!(lines[2].ends_with("\tTester\t171\tscope\tbackend\tfrontend/ui"));
+
+    assert_error!(
+        "todo mv 172 scope:frontend/ui id:171 name:\"new title\"" => (4, "error: file `target/test_mv/issues/frontend/ui/M.171.new title.md` already exists"),
+        "todo mv 179 name:x" => (3, "error: issue `179` is not found"),
+        "todo mv 172 color:red" => (2, "error: unknown command param `color`"),
+        "todo mv 172 name:../../x" => (2, "error: invalid path value `../../x`"),
+        "todo mv 172 scope:/tmp" => (2, "error: invalid path value `/tmp`")
+    );
+
+    assert_output!(
+        "todo --dry-run mv 172 priority:N" => "would move target/test_mv/issues/M.172.other.md to target/test_mv/issues/N.172.other.md\n",
+        "todo undo" => "restored target/test_mv/todo.history\nmoved target/test_mv/issues/frontend/ui/M.171.new title.md back to target/test_mv/issues/backend/M.171.new title.md\n"
+    );
+    assert!(Path::new("target/test_mv/issues/M.172.other.md").exists());
+
+    assert!(Path::new("target/test_mv/issues/backend/M.171.new title.md").exists());
+
+    delete_file!("target/test_mv/issues/backend/M.171.new title.md");
+    delete_file!("target/test_mv/issues/M.172.other.md");
+}
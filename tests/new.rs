@@ -6,6 +6,7 @@ use std::{env, fs};
 #[test]
 fn create_new_issue() {
     env::set_var("TODO_HOME", "./");
+    disable_stamps!();
     fs::remove_dir_all("target/test_new")
         .expect("Can't remove test_new dir");
 
@@ -180,4 +181,63 @@ priority = "B"
         "#[id: ID]\n#[priority: A]\n#[assign: User]\n#[attr1: test]\n#[attr2: test]\n"
     );
     delete_file!("target/test_new/tasks/task.md");
+
+    //
+    // Testing creation with front matter attrs
+    //
+
+    create_file!("target/test_new/todo.toml", r#"
+[store.fs]
+issues_dir = "target/test_new/tasks"
+format = "{scope:/}{name}{.:ext}"
+ext = "md"
+attrs_style = "yaml"
+
+[issue]
+attrs_order = ["id", "priority"]
+"#
+    );
+
+    run!("todo new id:ID top:A ctx:\"test: 1\" task");
+    assert_content!(
+        "target/test_new/tasks/task.md",
+        "---\nid: ID\npriority: A\nctx: \"test: 1\"\n---\n"
+    );
+    delete_file!("target/test_new/tasks/task.md");
+
+    run!("todo new id:ID top:A ctx:test attrs_style:toml task");
+    assert_content!(
+        "target/test_new/tasks/task.md",
+        "+++\nid = \"ID\"\npriority = \"A\"\nctx = \"test\"\n+++\n"
+    );
+    delete_file!("target/test_new/tasks/task.md");
+
+    run!("todo new id:ID top:A ctx:test attrs_style:attr task");
+    assert_content!(
+        "target/test_new/tasks/task.md",
+        "#[id: ID]\n#[priority: A]\n#[ctx: test]\n"
+    );
+    delete_file!("target/test_new/tasks/task.md");
+
+    //
+    // Testing creation with multi-valued attrs
+    //
+
+    create_file!("target/test_new/todo.toml", r#"
+[store.fs]
+issues_dir = "target/test_new/tasks"
+format = "{scope:/}{name}{.:ext}"
+ext = "md"
+
+[issue]
+multi_value_attrs = ["tags", "assign"]
+"#
+    );
+
+    run!("todo new tags:b,a,,c assign:\"User 1, User 2\" ctx:a,b task");
+    assert_content!(
+        "target/test_new/tasks/task.md",
+        "#[tags: b, a, c]\n#[assign: User 1, User 2]\n#[ctx: a,b]\n"
+    );
+    delete_file!("target/test_new/tasks/task.md");
 }
@@ -0,0 +1,73 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn stamp_issues() {
+    env::set_var("TODO_HOME", "./");
+    env::set_var("TODO_SET_USER", "Tester");
+    fs::remove_dir_all("target/test_stamp")
+        .expect("Can't remove test_stamp dir");
+
+    //
+    // Test stamping new issues with the default stamp attrs
+    //
+
+    run!("todo new task_stamp");
+    let content = fs::read_to_string("issues/task_stamp.md")
+        .expect("Can't read issues/task_stamp.md");
+    let lines: Vec<&str> = content.lines().collect();
+    assert_eq!(3, lines.len());
+    assert!(lines[0].starts_with("#[created: 20"));
+    assert_eq!(lines[0].replace("created", "updated"), lines[1]);
+    assert_eq!("#[author: Tester]", lines[2]);
+    delete_file!("issues/task_stamp.md");
+
+    run!("todo new created:2018-06-04 author:Someone task_stamp");
+    let content = fs::read_to_string("issues/task_stamp.md")
+        .expect("Can't read issues/task_stamp.md");
+    assert!(content.starts_with("#[created: 2018-06-04]\n#[author: Someone]\n#[updated: 20"));
+    delete_file!("issues/task_stamp.md");
+
+    env::set_var("TODO_SET_COMMAND.NEW.CREATED_ATTR", "");
+    env::set_var("TODO_SET_COMMAND.NEW.AUTHOR_ATTR", "");
+    run!("todo new task_stamp");
+    let content = fs::read_to_string("issues/task_stamp.md")
+        .expect("Can't read issues/task_stamp.md");
+    assert!(content.starts_with("#[updated: 20"));
+    assert_eq!(1, content.lines().count());
+    delete_file!("issues/task_stamp.md");
+    env::remove_var("TODO_SET_COMMAND.NEW.CREATED_ATTR");
+    env::remove_var("TODO_SET_COMMAND.NEW.AUTHOR_ATTR");
+
+    //
+    // Test stamping updated issues
+    //
+
+    create_file!("target/test_stamp/issues/S.31.task1.md", "#[updated: 2018-06-04 10:00:00]\ntext\n");
+    create_file!("target/test_stamp/issues/S.32.task2.md", "#[updated: 2018-06-05 10:00:00]\ntext\n");
+
+    assert_output!(
+        "todo list top:S sort:-updated columns:updated" => r#"
+2018-06-05 10:00:00 target/test_stamp/issues/S.32.task2.md
+2018-06-04 10:00:00 target/test_stamp/issues/S.31.task1.md
+"#
+    );
+
+    assert_output!(
+        "todo tag 31 +x" => "target/test_stamp/issues/S.31.task1.md"
+    );
+    let content = fs::read_to_string("target/test_stamp/issues/S.31.task1.md")
+        .expect("Can't read target/test_stamp/issues/S.31.task1.md");
+    assert!(content.starts_with("#[updated: 20"));
+    assert!(!content.starts_with("#[updated: 2018"));
+    assert!(content.ends_with("\n#[tags: x]\ntext\n"));
+
+    assert_output!(
+        "todo tag 31 +x" => ""
+    );
+
+    delete_file!("target/test_stamp/issues/S.31.task1.md");
+    delete_file!("target/test_stamp/issues/S.32.task2.md");
+}
@@ -0,0 +1,75 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn tag_issues() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_tag")
+        .expect("Can't remove test_tag dir");
+
+    create_file!("target/test_tag/issues/A.1.task1.md", "#[tags: a]\ntext #body\n");
+    create_file!("target/test_tag/issues/B.2.task2.md", "text\n");
+    create_file!("target/test_tag/issues/C.3.task3.md", "---\nname: task 3\n---\n#x #body\n");
+
+    //
+    // Test editing tags
+    //
+
+    assert_output!(
+        "todo tag 1 +b -a c" => "target/test_tag/issues/A.1.task1.md"
+    );
+    assert_content!("target/test_tag/issues/A.1.task1.md", "#[tags: b, c]\ntext #body\n");
+
+    assert_output!(
+        "todo tag 2 +x,y -b" => "target/test_tag/issues/B.2.task2.md"
+    );
+    assert_content!("target/test_tag/issues/B.2.task2.md", "#[tags: x, y]\ntext\n");
+
+    assert_output!(
+        "todo tag 3 +x" => "target/test_tag/issues/C.3.task3.md"
+    );
+    assert_content!("target/test_tag/issues/C.3.task3.md", "---\nname: task 3\ntags: x\n---\n#x #body\n");
+
+    assert_output!(
+        "todo tag 2 -x -y" => "target/test_tag/issues/B.2.task2.md"
+    );
+    assert_content!("target/test_tag/issues/B.2.task2.md", "text\n");
+
+    //
+    // Test listing tags
+    //
+
+    assert_output!(
+        "todo tags" => r#"
+body: 2
+x: 1
+b: 1
+c: 1
+"#
+    );
+
+    assert_output!(
+        "todo tags top:A" => r#"
+body: 1
+b: 1
+c: 1
+"#
+    );
+
+    assert_output!(
+        [
+            "todo list tags:body",
+            "todo list tags:b,x"
+        ] => r#"
+target/test_tag/issues/A.1.task1.md
+target/test_tag/issues/C.3.task3.md
+"#
+    );
+
+    delete_file!("target/test_tag/issues/A.1.task1.md");
+    delete_file!("target/test_tag/issues/B.2.task2.md");
+    delete_file!("target/test_tag/issues/C.3.task3.md");
+}
@@ -0,0 +1,74 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn taskwarrior_exchange() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_taskwarrior")
+        .expect("Can't remove test_taskwarrior dir");
+
+    create_file!("target/test_taskwarrior/todo.toml", "timezone = \"+02:00\"\n");
+    env::set_var("TODO_CONFIG_FILE_NAME", "target/test_taskwarrior/todo.toml");
+    create_file!("target/test_taskwarrior/issues/O.221.login.md", r#"#[tags: bug]
+#[uuid: 5f1c]
+text
+
+## Comments
+
+### 2024-01-03 11:30:00 alice
+Reproduced
+"#);
+
+    assert_output!(
+        "todo export O.221 --format taskwarrior" => r#"[
+  {
+    "uuid": "5f1c",
+    "description": "login",
+    "status": "pending",
+    "tags": [
+      "bug"
+    ],
+    "annotations": [
+      {
+        "entry": "20240103T093000Z",
+        "description": "Reproduced"
+      }
+    ]
+  }
+]"#
+    );
+
+    create_file!("target/test_taskwarrior/todo.toml", r#"
+timezone = "+02:00"
+
+[store.fs]
+issues_dir = "target/test_taskwarrior/imported"
+"#
+    );
+    create_file!("target/test_taskwarrior/tasks.json", r#"[
+  {"id": 1, "uuid": "9a2b", "description": "write docs", "status": "pending", "project": "web", "priority": "M",
+   "tags": ["docs"], "entry": "20240101T080000Z",
+   "annotations": [{"entry": "20240102T101500Z", "description": "see wiki"}]},
+  {"id": 0, "uuid": "77aa", "description": "gone", "status": "deleted"}
+]"#
+    );
+
+    assert_output!(
+        "todo import --format taskwarrior target/test_taskwarrior/tasks.json" =>
+            "target/test_taskwarrior/imported/web/M.write docs.md"
+    );
+    assert_content!("target/test_taskwarrior/imported/web/M.write docs.md", r#"#[uuid: 9a2b]
+#[tags: docs]
+## Comments
+
+### 2024-01-02 12:15:00 taskwarrior
+see wiki
+"#);
+    env::remove_var("TODO_CONFIG_FILE_NAME");
+
+    delete_file!("target/test_taskwarrior/issues/O.221.login.md");
+    delete_file!("target/test_taskwarrior/imported/web/M.write docs.md");
+}
@@ -0,0 +1,48 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn new_from_template() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_template")
+        .expect("Can't remove test_template dir");
+
+    create_file!("target/test_template/todo.toml", r##"
+[store.fs]
+issues_dir = "issues"
+ext = "md"
+
+[command.new]
+templates_dir = "target/test_template/templates"
+
+[command.new.templates]
+feature = "#[tags: feature]\nAs a user I want {name}.\n"
+"##
+    );
+    env::set_var("TODO_CONFIG_FILE_NAME", "target/test_template/todo.toml");
+    create_file!(
+        "target/test_template/templates/bug.md",
+        "#[tags: bug]\n#[due: 2018-06-04]\n## Bug {id}: {name}\n\nSteps:\n"
+    );
+
+    assert_output!(
+        "todo new template:bug priority:G id:161 crash" => "issues/G.161.crash.md",
+        "todo new template:bug priority:G id:162 due:2018-07-01 freeze" => "issues/G.162.freeze.md",
+        "todo new template:feature priority:G id:163 export" => "issues/G.163.export.md"
+    );
+    assert_content!("issues/G.161.crash.md", "#[tags: bug]\n#[due: 2018-06-04]\n## Bug 161: crash\n\nSteps:\n");
+    assert_content!("issues/G.162.freeze.md", "#[due: 2018-07-01]\n#[tags: bug]\n## Bug 162: freeze\n\nSteps:\n");
+    assert_content!("issues/G.163.export.md", "#[tags: feature]\nAs a user I want export.\n");
+
+    assert_error!(
+        "todo new template:chore priority:G id:164 clean" => (3, "error: template `chore` is not found")
+    );
+
+    env::remove_var("TODO_CONFIG_FILE_NAME");
+    delete_file!("issues/G.161.crash.md");
+    delete_file!("issues/G.162.freeze.md");
+    delete_file!("issues/G.163.export.md");
+}
@@ -0,0 +1,73 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn track_time() {
+    env::set_var("TODO_HOME", "./");
+    env::set_var("TODO_SET_TIME.LOG_FILE", "target/test_time/todo.time");
+    fs::remove_dir_all("target/test_time")
+        .expect("Can't remove test_time dir");
+
+    create_file!("target/test_time/issues/dev/W.81.task1.md", "#[assignee: alice]\n");
+    create_file!("target/test_time/issues/dev/W.82.task2.md", "#[assignee: bob]\n");
+    create_file!("target/test_time/issues/ops/W.83.task3.md", "#[assignee: alice]\n");
+
+    //
+    // Test logging time
+    //
+
+    assert_output!(
+        "todo log 81 1h30m date:2018-06-04" => "logged 81 1h30m",
+        "todo log 82 45m date:2018-06-05" => "logged 82 45m",
+        "todo log 83 30m date:2018-06-10" => "logged 83 30m",
+        "todo log 99 1h" => "",
+        "todo log 81 1x" => ""
+    );
+
+    //
+    // Test starting and stopping time tracking
+    //
+
+    assert_output!(
+        "todo start 82" => "started 82",
+        "todo start 81" => r#"
+stopped 82 0m
+started 81
+"#,
+        "todo stop" => "stopped 81 0m",
+        "todo stop" => "",
+        "todo start 99" => ""
+    );
+
+    //
+    // Test time report
+    //
+
+    assert_output!(
+        "todo report top:W from:2018-06-04 to:2018-06-05" => r#"
+81 task1: 1h30m
+82 task2: 45m
+total: 2h15m
+"#,
+        "todo report top:W by:assignee to:2018-06-30" => r#"
+alice: 2h
+bob: 45m
+total: 2h45m
+"#,
+        "todo report top:W scope to:2018-06-30" => r#"
+dev: 2h15m
+ops: 30m
+total: 2h45m
+"#,
+        "todo report top:W by:scope from:2018-06-06 to:2018-06-30" => r#"
+ops: 30m
+total: 30m
+"#
+    );
+
+    delete_file!("target/test_time/issues/dev/W.81.task1.md");
+    delete_file!("target/test_time/issues/dev/W.82.task2.md");
+    delete_file!("target/test_time/issues/ops/W.83.task3.md");
+}
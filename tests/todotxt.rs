@@ -0,0 +1,42 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn todotxt_exchange() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_todotxt")
+        .expect("Can't remove test_todotxt dir");
+
+    create_file!("target/test_todotxt/issues/K.211.call.md", "#[ctx: phone]\n#[tags: a, b]\ntext\n");
+
+    assert_output!(
+        "todo export K.211 --format todotxt" => "(K) call @phone tags:a,b id:211"
+    );
+
+    create_file!("target/test_todotxt/todo.toml", r#"
+[store.fs]
+issues_dir = "target/test_todotxt/imported"
+"#
+    );
+    env::set_var("TODO_CONFIG_FILE_NAME", "target/test_todotxt/todo.toml");
+    create_file!("target/test_todotxt/todo.txt", "(B) 2024-01-01 buy milk +home @shop @city id:212\n\nx 2024-01-03 pay bills pri:C\n");
+
+    assert_output!(
+        "todo import --format todotxt target/test_todotxt/todo.txt" =>
+            "target/test_todotxt/imported/home/B.212.buy milk.md\ntarget/test_todotxt/imported/C.pay bills.md"
+    );
+    assert_content!("target/test_todotxt/imported/home/B.212.buy milk.md", "#[ctx: shop, city]\n");
+    assert_content!("target/test_todotxt/imported/C.pay bills.md", "#[closed: 2024-01-03]\n");
+
+    assert_error!(
+        "todo import --format csv target/test_todotxt/todo.txt" => (2, "error: unsupported exchange format `csv`")
+    );
+    env::remove_var("TODO_CONFIG_FILE_NAME");
+
+    delete_file!("target/test_todotxt/issues/K.211.call.md");
+    delete_file!("target/test_todotxt/imported/home/B.212.buy milk.md");
+    delete_file!("target/test_todotxt/imported/C.pay bills.md");
+}
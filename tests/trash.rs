@@ -0,0 +1,46 @@
+#[macro_use]
+mod common;
+
+use std::path::Path;
+use std::{env, fs};
+
+#[test]
+fn trash_issues() {
+    env::set_var("TODO_HOME", "./");
+    env::set_var("TODO_SET_JOURNAL.FILE", "target/test_trash/todo.journal");
+    fs::remove_dir_all("target/test_trash")
+        .expect("Can't remove test_trash dir");
+
+    create_file!("target/test_trash/issues/app/T.181.task.md", "#[tags: a]\ntext\n");
+    create_file!("target/test_trash/issues/T.182.other.md", "");
+
+    assert_output!(
+        "todo rm 181" => "target/test_trash/issues/.trash/app/T.181.task.md",
+        "todo list 181" => "",
+        "todo trash" => "target/test_trash/issues/.trash/app/T.181.task.md",
+        "todo trash other" => ""
+    );
+    assert!(!Path::new("target/test_trash/issues/app").exists());
+
+    assert_output!(
+        "todo --dry-run restore 181" => "would move target/test_trash/issues/.trash/app/T.181.task.md to target/test_trash/issues/app/T.181.task.md\n",
+        "todo restore 181" => "target/test_trash/issues/app/T.181.task.md",
+        "todo trash" => ""
+    );
+    assert_content!("target/test_trash/issues/app/T.181.task.md", "#[tags: a]\ntext\n");
+    assert!(!Path::new("target/test_trash/issues/.trash/app").exists());
+
+    assert_error!(
+        "todo restore 181" => (3, "error: issue `181` is not found"),
+        "todo rm 189" => (3, "error: issue `189` is not found")
+    );
+
+    assert_output!(
+        "todo rm 182 --purge" => "deleted target/test_trash/issues/T.182.other.md",
+        "todo undo" => "recreated target/test_trash/issues/T.182.other.md\n"
+    );
+    assert!(Path::new("target/test_trash/issues/T.182.other.md").exists());
+
+    delete_file!("target/test_trash/issues/app/T.181.task.md");
+    delete_file!("target/test_trash/issues/T.182.other.md");
+}
@@ -0,0 +1,54 @@
+#[macro_use]
+mod common;
+
+use std::{env, fs};
+
+#[test]
+fn tree_issues() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    fs::remove_dir_all("target/test_tree")
+        .expect("Can't remove test_tree dir");
+
+    //
+    // Test creating subtasks
+    //
+
+    run!("todo new parent:61 task_child");
+    assert_content!("issues/task_child.md", "#[parent: 61]\n");
+    delete_file!("issues/task_child.md");
+
+    //
+    // Test printing hierarchy
+    //
+
+    create_file!("target/test_tree/issues/T.61.release.md", "");
+    create_file!("target/test_tree/issues/T.62.docs.md", "#[parent: 61]\n#[closed: 2018-06-04]\n");
+    create_file!("target/test_tree/issues/T.63.tests.md", "#[parent: 61]\n");
+    create_file!("target/test_tree/issues/T.64.unit.md", "#[parent: 63]\n");
+    create_file!("target/test_tree/issues/T.65.other.md", "");
+
+    assert_output!(
+        "todo tree top:T" => r#"
+61 release [1/2]
+  62 docs [closed]
+  63 tests [0/1]
+    64 unit
+65 other
+"#
+    );
+
+    assert_output!(
+        "todo show 61" => r#"
+children: 1/2
+  62 docs [closed]
+  63 tests [0/1]
+"#
+    );
+
+    delete_file!("target/test_tree/issues/T.61.release.md");
+    delete_file!("target/test_tree/issues/T.62.docs.md");
+    delete_file!("target/test_tree/issues/T.63.tests.md");
+    delete_file!("target/test_tree/issues/T.64.unit.md");
+    delete_file!("target/test_tree/issues/T.65.other.md");
+}
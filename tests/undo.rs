@@ -0,0 +1,94 @@
+#[macro_use]
+mod common;
+
+use std::path::Path;
+use std::{env, fs};
+
+#[test]
+fn undo_operations() {
+    env::set_var("TODO_HOME", "./");
+    disable_stamps!();
+    env::set_var("TODO_SET_JOURNAL.FILE", "target/test_undo/todo.journal");
+    env::set_var("TODO_SET_HISTORY.FILE", "target/test_undo/todo.history");
+    env::set_var("TODO_SET_TIME.LOG_FILE", "target/test_undo/todo.time");
+    fs::remove_dir_all("target/test_undo")
+        .expect("Can't remove test_undo dir");
+
+    create_file!("target/test_undo/todo.toml", r#"
+[store.fs]
+issues_dir = "issues"
+ext = "md"
+id_generator = "sequence"
+
+[generator.sequence]
+required = true
+file = "target/test_undo/todo.seq"
+"#
+    );
+    env::set_var("TODO_CONFIG_FILE_NAME", "target/test_undo/todo.toml");
+    create_file!("target/test_undo/todo.seq", "110");
+    create_file!("target/test_undo/issues/U.100.task.md", "#[tags: a]\ntext\n");
+
+    assert_output!(
+        "todo undo" => ""
+    );
+
+    //
+    // Test undo of creating issue
+    //
+
+    assert_output!(
+        "todo new priority:U chore" => "issues/U.110.chore.md"
+    );
+    assert_content!("target/test_undo/todo.seq", "111");
+
+    assert_output!(
+        "todo undo" => "removed issues/U.110.chore.md\nreset target/test_undo/todo.seq to 110\n"
+    );
+    assert!(!Path::new("issues/U.110.chore.md").exists());
+    assert_content!("target/test_undo/todo.seq", "110");
+
+    //
+    // Test undo of updating issue
+    //
+
+    assert_output!(
+        "todo tag 100 +b -a" => "target/test_undo/issues/U.100.task.md"
+    );
+    assert_content!("target/test_undo/issues/U.100.task.md", "#[tags: b]\ntext\n");
+
+    assert_output!(
+        "todo undo" => "restored target/test_undo/todo.history\nrestored target/test_undo/issues/U.100.task.md\n",
+        "todo undo" => ""
+    );
+    assert_content!("target/test_undo/issues/U.100.task.md", "#[tags: a]\ntext\n");
+    assert_content!("target/test_undo/todo.history", "");
+
+    //
+    // Test undo of logging time
+    //
+
+    assert_output!(
+        "todo log 100 1h" => "logged 100 1h"
+    );
+    assert_output!(
+        "todo undo" => "restored target/test_undo/todo.time\n"
+    );
+    assert_content!("target/test_undo/todo.time", "");
+
+    //
+    // Test refusing undo of changed file
+    //
+
+    assert_output!(
+        "todo tag 100 +c" => "target/test_undo/issues/U.100.task.md"
+    );
+    create_file!("target/test_undo/issues/U.100.task.md", "#[tags: a, c]\nedited\n");
+    assert_output!(
+        "todo undo" => ""
+    );
+    assert_content!("target/test_undo/issues/U.100.task.md", "#[tags: a, c]\nedited\n");
+
+    env::remove_var("TODO_CONFIG_FILE_NAME");
+    delete_file!("target/test_undo/issues/U.100.task.md");
+}